@@ -1,5 +1,14 @@
+mod validate;
+
+use crate::{
+    APP_CONFIG_PATH,
+    error::{Error, Result},
+};
+use manaba_sdk::{Tz, assignment::UrgencyPolicy};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use toml_edit::DocumentMut;
+pub use validate::validate;
 
 const DEFAULT_BASE_URL: &str = "https://ct.ritsumei.ac.jp/ct/";
 const DEFAULT_COOKIE_DOMAIN: &str = "ct.ritsumei.ac.jp";
@@ -8,8 +17,97 @@ const DEFAULT_COOKIE_DOMAIN: &str = "ct.ritsumei.ac.jp";
 pub struct AppConfig {
     pub base_url: String,
     pub cookie_domain: String,
+    /// External fuzzy finder such as `fzf`, the built-in one is used when unset
+    #[serde(default)]
+    pub finder: Option<String>,
     pub timetable: HashMap<String, String>,
     pub color: HashMap<String, String>,
+    #[serde(default)]
+    pub templates: HashMap<String, String>,
+    #[serde(default)]
+    pub notify: NotifyConfig,
+    #[serde(default)]
+    pub urgency: UrgencyPolicy,
+    #[serde(default)]
+    pub timezone: TimezoneConfig,
+    /// Preferences by course id such as `course_12345`
+    #[serde(default)]
+    pub courses: HashMap<String, CoursePrefs>,
+    pub smtp: Option<SmtpConfig>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct NotifyConfig {
+    /// Hours before a deadline to send a reminder
+    pub lead_hours: Vec<u64>,
+    /// Send desktop notifications
+    pub desktop: bool,
+    pub webhook: Option<WebhookConfig>,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            lead_hours: vec![24, 3],
+            desktop: true,
+            webhook: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct TimezoneConfig {
+    /// Time zone manaba shows deadlines in
+    pub institution: Tz,
+    /// Time zone to show deadlines in, the system time zone when unset
+    pub display: Option<Tz>,
+}
+
+impl Default for TimezoneConfig {
+    fn default() -> Self {
+        Self {
+            institution: Tz::Asia__Tokyo,
+            display: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct CoursePrefs {
+    /// Leave the course out of every listing
+    pub hidden: bool,
+    /// List the course before the others
+    pub pinned: bool,
+    /// Shown instead of the course title
+    pub alias: Option<String>,
+    /// Name of a color in `[color]` or a hex color
+    pub color: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct WebhookConfig {
+    pub url: String,
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// Number of retries after a failed request
+    #[serde(default = "default_webhook_retries")]
+    pub retries: u32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    #[default]
+    Json,
+    Slack,
+    Discord,
+}
+
+fn default_webhook_retries() -> u32 {
+    3
 }
 
 impl Default for AppConfig {
@@ -17,8 +115,86 @@ impl Default for AppConfig {
         Self {
             base_url: DEFAULT_BASE_URL.to_owned(),
             cookie_domain: DEFAULT_COOKIE_DOMAIN.to_owned(),
+            finder: None,
             timetable: Default::default(),
             color: Default::default(),
+            templates: Default::default(),
+            notify: Default::default(),
+            urgency: Default::default(),
+            timezone: Default::default(),
+            courses: Default::default(),
+            smtp: None,
         }
     }
 }
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTls {
+    None,
+    #[default]
+    Starttls,
+    Tls,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+/// Write a config file with the default values to `path`
+pub fn write_default_config(path: &std::path::Path) -> Result<AppConfig> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let app_config = AppConfig::default();
+    std::fs::write(path, toml::to_string(&app_config).unwrap())?;
+
+    Ok(app_config)
+}
+
+/// Edit the config file in place, keeping comments and formatting
+pub fn edit_config_file(edit: impl FnOnce(&mut DocumentMut) -> Result<()>) -> Result<()> {
+    let path = APP_CONFIG_PATH.get().unwrap();
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(Error::from(e)),
+    };
+
+    let mut document = content.parse::<DocumentMut>()?;
+    edit(&mut document)?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, document.to_string())?;
+
+    Ok(())
+}
+
+/// Get a table of the config file, creating it when missing
+pub fn table_mut<'a>(
+    table: &'a mut toml_edit::Table,
+    key: &str,
+) -> Result<&'a mut toml_edit::Table> {
+    table
+        .entry(key)
+        .or_insert_with(toml_edit::table)
+        .as_table_mut()
+        .ok_or_else(|| Error::ConfigEdit(format!("`{key}` is not a table")))
+}
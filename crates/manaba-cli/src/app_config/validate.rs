@@ -0,0 +1,290 @@
+use super::AppConfig;
+use crate::{
+    cmd::{parse_period, parse_time_range},
+    color::{COLOR_NAMES, is_hex_color},
+    template::Template,
+};
+use std::{fmt, ops::Range};
+use toml_edit::ImDocument;
+
+/// A problem in the config file, with the position it was found at
+pub struct Issue {
+    /// 1-based line and column
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some((line, column)) => write!(f, "{line}:{column}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Check the content of a config file, beyond what deserializing it catches
+///
+/// Syntax and type errors stop the check, since the rest cannot be read reliably.
+pub fn validate(content: &str) -> Vec<Issue> {
+    let document = match ImDocument::parse(content) {
+        Ok(document) => document,
+        Err(e) => return vec![Issue::at(content, e.span(), one_line(e.message()))],
+    };
+
+    let mut unknown_keys = Vec::new();
+    let config: AppConfig =
+        match serde_ignored::deserialize(toml::Deserializer::new(content), |path| {
+            unknown_keys.push(segments(&path))
+        }) {
+            Ok(config) => config,
+            Err(e) => return vec![Issue::at(content, e.span(), one_line(e.message()))],
+        };
+
+    let checker = Checker {
+        content,
+        document: &document,
+    };
+    let mut issues = Vec::new();
+
+    for path in unknown_keys {
+        issues.push(checker.issue(&path, format!("unknown key `{}`", path.join("."))));
+    }
+
+    match reqwest::Url::parse(&config.base_url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {}
+        Ok(_) => {
+            issues.push(checker.issue(&["base_url"], "`base_url` must be an http or https URL"))
+        }
+        Err(e) => issues.push(checker.issue(&["base_url"], format!("invalid `base_url`: {e}"))),
+    }
+
+    if config.cookie_domain.is_empty() || config.cookie_domain.contains('/') {
+        issues.push(checker.issue(
+            &["cookie_domain"],
+            "`cookie_domain` must be a host name such as ct.ritsumei.ac.jp",
+        ));
+    }
+
+    for (name, color) in &config.color {
+        if !COLOR_NAMES.contains(&name.as_str()) {
+            issues.push(checker.issue(
+                &["color", name],
+                format!(
+                    "unknown color `{name}`, expected one of {}",
+                    COLOR_NAMES.join(", ")
+                ),
+            ));
+        } else if !is_hex_color(color) {
+            issues.push(checker.issue(
+                &["color", name],
+                format!("`{color}` is not a hex color such as D3C6AA"),
+            ));
+        }
+    }
+
+    for (id, prefs) in &config.courses {
+        if let Some(color) = &prefs.color
+            && !COLOR_NAMES.contains(&color.as_str())
+            && !is_hex_color(color)
+        {
+            issues.push(checker.issue(
+                &["courses", id, "color"],
+                format!("`{color}` is neither a name in [color] nor a hex color"),
+            ));
+        }
+    }
+
+    for (key, time) in &config.timetable {
+        let rest = key.trim_start_matches(|c: char| c.is_ascii_digit());
+        if parse_period(key).is_none() || !matches!(rest, "" | "限") {
+            issues.push(checker.issue(
+                &["timetable", key],
+                format!("`{key}` is not a period such as 1 or 1限"),
+            ));
+        } else if parse_time_range(time).is_none() {
+            issues.push(checker.issue(
+                &["timetable", key],
+                format!("`{time}` is not a time range such as 9:00 ~ 10:35"),
+            ));
+        }
+    }
+
+    if let Err(e) = config.urgency.validate() {
+        issues.push(checker.issue(&["urgency", "high_hours"], e));
+    }
+
+    for (name, template) in &config.templates {
+        if let Err(e) = Template::parse(template) {
+            issues.push(checker.issue(&["templates", name], e.to_string()));
+        }
+    }
+
+    issues.sort_by_key(|issue| issue.position);
+    issues
+}
+
+impl Issue {
+    fn at(content: &str, span: Option<Range<usize>>, message: impl Into<String>) -> Self {
+        Self {
+            position: span.map(|span| position(content, span.start)),
+            message: message.into(),
+        }
+    }
+}
+
+struct Checker<'a> {
+    content: &'a str,
+    document: &'a ImDocument<&'a str>,
+}
+
+impl Checker<'_> {
+    /// An issue at the value of `path`, or at the closest table that exists
+    fn issue<S: AsRef<str>>(&self, path: &[S], message: impl Into<String>) -> Issue {
+        let mut item = self.document.as_item();
+        let mut span = None;
+
+        for key in path {
+            let Some(next) = item.get(key.as_ref()) else {
+                break;
+            };
+            item = next;
+            span = item.span().or(span);
+        }
+
+        Issue::at(self.content, span, message)
+    }
+}
+
+/// Keys leading to an ignored value
+fn segments(path: &serde_ignored::Path) -> Vec<String> {
+    match path {
+        serde_ignored::Path::Root => Vec::new(),
+        serde_ignored::Path::Seq { parent, index } => {
+            let mut segments = segments(parent);
+            segments.push(index.to_string());
+            segments
+        }
+        serde_ignored::Path::Map { parent, key } => {
+            let mut segments = segments(parent);
+            segments.push(key.clone());
+            segments
+        }
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => segments(parent),
+    }
+}
+
+fn one_line(message: &str) -> String {
+    message.trim().lines().collect::<Vec<_>>().join(", ")
+}
+
+fn position(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = r#"base_url = "https://ct.ritsumei.ac.jp/ct/"
+cookie_domain = "ct.ritsumei.ac.jp"
+
+[timetable]
+1 = "9:00 ~ 10:35"
+
+[color]
+red = "E67E80"
+"#;
+
+    /// Issues of `VALID` with `extra` appended, as positions and messages
+    fn issues_with(extra: &str) -> Vec<(Option<(usize, usize)>, String)> {
+        validate(&format!("{VALID}{extra}"))
+            .into_iter()
+            .map(|issue| (issue.position, issue.message))
+            .collect()
+    }
+
+    #[test]
+    fn valid_test() {
+        assert!(validate(VALID).is_empty());
+    }
+
+    #[test]
+    fn syntax_error_test() {
+        let issues = validate("base_url = \"https://ct.ritsumei.ac.jp/ct/\"\ncookie_domain = \n");
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].position, Some((2, 17)));
+    }
+
+    #[test]
+    fn unknown_key_test() {
+        let issues = issues_with("\n[notify]\nlead_hour = [24]\n");
+        assert_eq!(
+            issues,
+            [(Some((11, 13)), "unknown key `notify.lead_hour`".to_owned())]
+        );
+
+        // `[color]` accepts any key, so unknown names are caught separately
+        let issues = issues_with("yellow = \"DBBC7F\"\nbrown = \"A0522D\"\n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].0, Some((10, 9)));
+        assert!(issues[0].1.starts_with("unknown color `brown`"));
+    }
+
+    #[test]
+    fn hex_color_test() {
+        let issues = issues_with("blue = \"#7FBBB\"\n");
+
+        assert_eq!(
+            issues,
+            [(
+                Some((9, 8)),
+                "`#7FBBB` is not a hex color such as D3C6AA".to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn timetable_key_test() {
+        let content = VALID.replace("1 = ", "\"1時\" = ");
+        let issues = validate(&content);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "`1時` is not a period such as 1 or 1限");
+        assert_eq!(issues[0].position, Some((5, 8)));
+    }
+
+    #[test]
+    fn urgency_test() {
+        let issues = issues_with("\n[urgency]\nhigh_hours = 100\nmedium_hours = 24\n");
+
+        assert_eq!(
+            issues,
+            [(
+                Some((11, 14)),
+                "high_hours (100) must not be greater than medium_hours (24)".to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn multi_byte_column_test() {
+        // The column counts characters, not the three bytes of 限
+        let content = VALID.replace("1 = \"9:00 ~ 10:35\"", "\"1限\" = \"9:00\"");
+        let issues = validate(&content);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].message,
+            "`9:00` is not a time range such as 9:00 ~ 10:35"
+        );
+        assert_eq!(issues[0].position, Some((5, 8)));
+    }
+}
@@ -0,0 +1,187 @@
+use crate::{
+    cmd::{AssignmentFilter, exam_records, report_records, todo_records},
+    courses,
+    error::{Result, print_err},
+    output::AssignmentRecord,
+    overlay, timezone, urgency,
+};
+use chrono::Utc;
+use manaba_sdk::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Detail pages rarely change once published, so their text is kept for a day
+const PAGE_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    /// Unix timestamp of the last successful fetch
+    fetched_at: i64,
+    assignments: Vec<AssignmentRecord>,
+    /// Course ids and titles, for shell completion
+    #[serde(default)]
+    courses: Vec<(String, String)>,
+}
+
+/// Text of a detail page, for picker previews
+#[derive(Serialize, Deserialize)]
+struct CachedPage {
+    /// Unix timestamp of the fetch
+    fetched_at: i64,
+    lines: Vec<String>,
+}
+
+pub fn cache_path() -> PathBuf {
+    let cache_dir_path = dirs::cache_dir().unwrap();
+    cache_dir_path.join("manaba").join("assignments.json")
+}
+
+fn page_cache_path() -> PathBuf {
+    let cache_dir_path = dirs::cache_dir().unwrap();
+    cache_dir_path.join("manaba").join("pages.json")
+}
+
+/// Load all cached assignments when the cache is younger than `max_age`
+pub fn load(max_age: Duration) -> Option<Vec<AssignmentRecord>> {
+    let cache = read()?;
+    let age = Utc::now().timestamp() - cache.fetched_at;
+
+    if age < 0 || age as u64 > max_age.as_secs() {
+        return None;
+    }
+
+    Some(with_todos(with_current_importance(cache.assignments)))
+}
+
+/// Load all cached assignments regardless of their age
+pub fn load_stale() -> Option<Vec<AssignmentRecord>> {
+    read().map(|cache| with_todos(with_current_importance(cache.assignments)))
+}
+
+/// Fetch all assignments from manaba and store them in the cache, todos are never cached
+pub async fn refresh(client: &Client) -> Result<Vec<AssignmentRecord>> {
+    let filter = AssignmentFilter::all();
+    let mut assignments = report_records(client, &filter).await?;
+    assignments.extend(exam_records(client, &filter).await?);
+    let courses = courses::courses(client)
+        .await?
+        .into_iter()
+        .map(|course| (course.id, course.title))
+        .collect();
+
+    let cache = CacheFile {
+        fetched_at: Utc::now().timestamp(),
+        assignments,
+        courses,
+    };
+
+    if let Err(e) = write(&cache) {
+        print_err(format!("Failed to write cache: {e}"));
+    }
+
+    Ok(with_todos(cache.assignments))
+}
+
+/// Text of the detail pages at `ids`, fetched unless cached within [`PAGE_MAX_AGE`]
+///
+/// Pages that fail to load fall back to their last cached text, or are left out.
+pub async fn page_texts(client: &Client, ids: &[&str]) -> BTreeMap<String, Vec<String>> {
+    let path = page_cache_path();
+    let mut pages: BTreeMap<String, CachedPage> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let now = Utc::now().timestamp();
+    let mut changed = false;
+
+    for &id in ids {
+        let is_fresh = pages.get(id).is_some_and(|page| {
+            (0..=PAGE_MAX_AGE.as_secs() as i64).contains(&(now - page.fetched_at))
+        });
+        if is_fresh {
+            continue;
+        }
+
+        if let Ok(page) = client.get_page_text(id).await {
+            pages.insert(
+                id.to_owned(),
+                CachedPage {
+                    fetched_at: now,
+                    lines: page.lines,
+                },
+            );
+            changed = true;
+        }
+    }
+
+    if changed {
+        let result = serde_json::to_string(&pages)
+            .map_err(Into::into)
+            .and_then(|content| write_file(&path, &content));
+        if let Err(e) = result {
+            print_err(format!("Failed to write cache: {e}"));
+        }
+    }
+
+    ids.iter()
+        .filter_map(|&id| Some((id.to_owned(), pages.remove(id)?.lines)))
+        .collect()
+}
+
+/// Cached course ids and titles as they are, which needs no config
+pub fn cached_courses() -> Vec<(String, String)> {
+    read().map(|cache| cache.courses).unwrap_or_default()
+}
+
+/// Cached assignments without recomputing anything, which needs no config
+pub fn cached_assignments() -> Vec<AssignmentRecord> {
+    read().map(|cache| cache.assignments).unwrap_or_default()
+}
+
+fn read() -> Option<CacheFile> {
+    let content = std::fs::read_to_string(cache_path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write(cache: &CacheFile) -> Result<()> {
+    write_file(&cache_path(), &serde_json::to_string(cache)?)
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Importance depends on the current time, dates on the display time zone and local
+/// states on `manaba mark`, so they are recomputed instead of trusting the cache
+fn with_current_importance(mut assignments: Vec<AssignmentRecord>) -> Vec<AssignmentRecord> {
+    for assignment in &mut assignments {
+        let local = overlay::local_state(&assignment.id);
+
+        assignment.start_date = assignment.start_date.as_ref().map(timezone::to_display);
+        assignment.due_date = assignment.due_date.as_ref().map(timezone::to_display);
+        assignment.importance = assignment.due_date.as_ref().map(urgency::importance_level);
+        assignment.local_status = local.and_then(|local| local.status);
+        assignment.note = local.and_then(|local| local.note.clone());
+    }
+
+    assignments
+}
+
+/// Append local todos, read fresh so ones added since the last fetch show up
+fn with_todos(mut assignments: Vec<AssignmentRecord>) -> Vec<AssignmentRecord> {
+    match todo_records(&AssignmentFilter::all()) {
+        Ok(todos) => assignments.extend(todos),
+        Err(e) => print_err(format!("Failed to load todos: {e}")),
+    }
+
+    assignments
+}
@@ -1,23 +1,202 @@
+mod agenda;
+mod complete;
+mod config;
 mod course;
+mod course_prefs;
+mod digest;
+mod doctor;
 mod exam;
+mod filter;
+mod mark;
+mod next;
+mod open;
 mod report;
+mod serve_ics;
+mod status;
 mod timetable;
+mod today;
+mod todo;
+mod tui;
+mod watch;
 
-use crate::{APP_CONFIG, APP_CONFIG_PATH, client, color::AppColorize as _, error::Result};
-use clap::{Parser, Subcommand};
-use course::course;
+use crate::{
+    APP_CONFIG, APP_CONFIG_PATH, client,
+    color::AppColorize as _,
+    error::{EXIT_FAILURE, EXIT_URGENT, Error, Result},
+    notify::Notifier,
+    output::{AssignmentRecord, OutputFormat, print_records},
+    template::Template,
+    term::{self, TermFilter},
+    timezone::{self, TimeArg},
+    urgency,
+};
+use clap::{CommandFactory as _, Parser, Subcommand};
+use clap_complete::{ArgValueCandidates, CompleteEnv, Shell};
+use config::config;
+use course::{course, course_records};
+use course_prefs::edit_course_prefs;
+use digest::digest;
+use doctor::doctor;
 use exam::exam;
+pub(crate) use exam::exam_records;
+pub(crate) use filter::AssignmentFilter;
+use filter::FilterArgs;
 use manaba_sdk::assignment::AssignmentDate;
 use manaba_sdk::assignment::{AssignmentImportanceLevel, AssignmentReceptibleState};
+use manaba_sdk::clock::FixedClock;
+use manaba_sdk::overlay::{LocalState, LocalStatus};
+use manaba_sdk::query::{AssignmentState, SortKey};
+use mark::{MarkStatus, mark};
+use next::next;
+use open::open;
 use report::report;
-use timetable::timetable;
+pub(crate) use report::report_records;
+use serve_ics::serve_ics;
+use status::status;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+pub(crate) use timetable::{parse_period, parse_time_range};
+use timetable::{timetable, timetable_records};
+use today::today;
+pub(crate) use todo::todo_records;
+use todo::{edit_todos, list_todos};
+use tui::tui;
+use watch::watch;
 
 const INDENT: &str = "   ";
 
 #[derive(Parser)]
-struct Cli {
+#[command(name = "manaba", version, about)]
+pub(crate) struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// output format of listings
+    #[arg(long, global = true, value_enum, default_value_t)]
+    format: OutputFormat,
+    /// one-line template for each assignment, or the name of a template in settings.toml
+    #[arg(long, global = true)]
+    template: Option<String>,
+    /// evaluate deadlines as of this time instead of now (e.g. 2026-10-25T09:00)
+    #[arg(long, global = true, value_parser = timezone::parse_date_time)]
+    as_of: Option<TimeArg>,
+    /// only include courses of a term: current, all, a year (2026) or a term (2026-first)
+    #[arg(long, global = true, value_parser = term::parse_term_filter)]
+    term: Option<TermFilter>,
+    /// never prompt and fail instead, implied when stdin is not a terminal
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+}
+
+impl Cli {
+    /// Whether the config is read, so that other commands never load it or prompt to create it
+    pub fn needs_config(&self) -> bool {
+        let reads_config = !matches!(
+            self.command,
+            Commands::Completions { .. }
+                | Commands::Man { .. }
+                | Commands::ConfigPath
+                | Commands::Config {
+                    action: ConfigAction::Init { .. }
+                        | ConfigAction::Path
+                        | ConfigAction::Validate
+                        | ConfigAction::Edit
+                        | ConfigAction::Set { .. }
+                }
+        );
+
+        // `--as-of` is read in the display time zone, and templates may be named in the config
+        reads_config || self.as_of.is_some() || self.template.is_some()
+    }
+}
+
+#[derive(Subcommand)]
+pub(crate) enum CourseAction {
+    /// Hide a course from every listing
+    Hide {
+        /// course id such as course_12345, or 12345
+        #[arg(add = ArgValueCandidates::new(complete::course_candidates))]
+        course: String,
+    },
+    /// Show a hidden course again
+    Unhide {
+        #[arg(add = ArgValueCandidates::new(complete::course_candidates))]
+        course: String,
+    },
+    /// List a course before the others
+    Pin {
+        #[arg(add = ArgValueCandidates::new(complete::course_candidates))]
+        course: String,
+    },
+    /// Stop listing a course first
+    Unpin {
+        #[arg(add = ArgValueCandidates::new(complete::course_candidates))]
+        course: String,
+    },
+    /// Show a course under another name, or remove the alias when omitted
+    Alias {
+        #[arg(add = ArgValueCandidates::new(complete::course_candidates))]
+        course: String,
+        alias: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum ConfigAction {
+    /// Print the config in effect, including defaults
+    Show,
+    /// Print one value, such as `color.red` or `timezone.display`
+    Get { key: String },
+    /// Set one value in the config file
+    Set {
+        /// dotted key such as `color.red`, quote keys containing dots
+        key: String,
+        /// TOML value, or a plain string
+        value: String,
+    },
+    /// Open the config file with $VISUAL or $EDITOR and check it afterwards
+    Edit,
+    /// Check the config file for mistakes
+    Validate,
+    /// Write a config file with the default values
+    Init {
+        /// overwrite an existing config file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Show the config file path
+    Path,
+}
+
+#[derive(Subcommand)]
+pub(crate) enum TodoAction {
+    /// Add a personal item, shown next to assignments
+    Add {
+        title: String,
+        /// course id, or part of its title or alias
+        #[arg(long, add = ArgValueCandidates::new(complete::course_candidates))]
+        course: Option<String>,
+        /// deadline such as 2026-10-20T18:00, or 2026-10-20 for the end of the day
+        #[arg(long, value_parser = timezone::parse_deadline)]
+        due: Option<TimeArg>,
+    },
+    /// Mark an item as done
+    Done {
+        /// todo id such as todo_1
+        #[arg(add = ArgValueCandidates::new(complete::todo_candidates))]
+        id: String,
+    },
+    /// Mark a done item as not done again
+    Undone {
+        #[arg(add = ArgValueCandidates::new(complete::todo_candidates))]
+        id: String,
+    },
+    /// Delete an item
+    #[command(visible_alias = "rm")]
+    Remove {
+        #[arg(add = ArgValueCandidates::new(complete::todo_candidates))]
+        id: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -26,69 +205,282 @@ enum Commands {
     Browse,
     /// Show manaba-cli config path
     ConfigPath,
+    /// Show, change or check the config file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Check the config, cookies, connection and scrapers when something does not work
+    Doctor,
+    /// Print a shell completion script
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Print the man page
+    Man {
+        /// write a page for every subcommand into this directory instead
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// Open the course, report, exam or news page best matching a title, or pick one
+    Open {
+        /// words of the title, optionally with the course title; pick from a list when omitted
+        #[arg(add = ArgValueCandidates::new(complete::title_candidates))]
+        query: Vec<String>,
+        /// print the URL instead of opening it
+        #[arg(long)]
+        print: bool,
+    },
     /// Browse and open courses
-    Course,
-    /// Show timetable
+    Course {
+        #[command(subcommand)]
+        action: Option<CourseAction>,
+    },
+    /// Track progress manaba does not know about, such as started or ignored
+    Mark {
+        /// assignment id such as course_12345_report_6789, or its URL
+        #[arg(add = ArgValueCandidates::new(complete::assignment_candidates))]
+        id: String,
+        #[arg(value_enum)]
+        status: Option<MarkStatus>,
+        /// attach a note, or remove it with an empty string
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// Keep personal todo items, or list them when no action is given
+    Todo {
+        #[command(subcommand)]
+        action: Option<TodoAction>,
+    },
+    /// Show weekly timetable
     Timetable,
+    /// Show today's classes and their open assignments
+    Today,
+    /// Show the next class and how long until it starts
+    Next,
     /// List reports
     Report {
-        #[arg(short, long)]
-        all: bool,
-        /// filter by approaching deadlines
-        #[arg(short, long)]
-        warn: bool,
+        #[command(flatten)]
+        filter: FilterArgs,
     },
     /// List exams
     Exam {
-        #[arg(short, long)]
-        all: bool,
-        /// filter by approaching deadlines
-        #[arg(short, long)]
-        warn: bool,
+        #[command(flatten)]
+        filter: FilterArgs,
     },
     /// List assignment include reports and exams
     Check {
-        #[arg(short, long)]
-        all: bool,
-        /// filter by approaching deadlines
-        #[arg(short, long)]
-        warn: bool,
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// merge reports and exams into one timeline by deadline
+        #[arg(long)]
+        agenda: bool,
+    },
+    /// Print a one-line summary of open assignments for status bars
+    Status {
+        /// print JSON for waybar custom modules
+        #[arg(long)]
+        json: bool,
+        /// print full text, short text and color lines for i3blocks
+        #[arg(long, conflicts_with = "json")]
+        i3blocks: bool,
+        /// maximum age of cached assignments in minutes
+        #[arg(long, default_value_t = 10)]
+        max_age: u64,
+        /// ignore the cache and fetch assignments from manaba
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Open a full-screen dashboard of courses and assignments
+    Tui {
+        /// refresh interval in minutes
+        #[arg(short, long, default_value_t = 15)]
+        interval: u64,
+    },
+    /// Send desktop notifications for new assignments and approaching deadlines
+    #[command(visible_alias = "daemon")]
+    Watch {
+        /// refresh interval in minutes
+        #[arg(short, long, default_value_t = 15)]
+        interval: u64,
+        /// check once and exit
+        #[arg(long)]
+        once: bool,
+        /// print notifications and webhook payloads instead of sending them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print or email a digest of open assignments, course news and changed deadlines
+    Digest {
+        /// send the digest through the SMTP server in the config file
+        #[arg(long)]
+        smtp: bool,
+    },
+    /// Serve an iCalendar feed of deadlines over HTTP
+    ServeIcs {
+        /// address to listen on
+        #[arg(short, long, default_value = "127.0.0.1:8080")]
+        bind: SocketAddr,
+        /// refresh interval in minutes
+        #[arg(short, long, default_value_t = 30)]
+        interval: u64,
     },
 }
 
-pub async fn cmd() -> Result<()> {
-    let cli = Cli::parse();
+/// Answer shell completion requests, which exits when `COMPLETE` is set
+pub fn complete() {
+    CompleteEnv::with_factory(Cli::command).complete();
+}
+
+pub async fn cmd(cli: Cli) -> Result<ExitCode> {
+    if let Some(as_of) = cli.as_of {
+        let as_of = as_of.resolve()?;
+        timezone::APP_CLOCK.get_or_init(|| Box::new(FixedClock(as_of)));
+    }
+    if let Some(term) = cli.term {
+        term::APP_TERM.get_or_init(|| term);
+    }
+    let format = cli.format;
+    let template = cli.template.as_deref().map(resolve_template).transpose()?;
+    let is_text = format == OutputFormat::Text && template.is_none();
 
     match cli.command {
-        Commands::Report { all, warn } => {
+        Commands::Report { filter } => {
+            let app_config = APP_CONFIG.get().unwrap();
+            let client = client(app_config).await?;
+            let filter = AssignmentFilter::new(&filter)?;
+
+            if is_text {
+                report(&client, &filter).await?;
+            } else {
+                let mut records = report_records(&client, &filter).await?;
+                filter.query.sort(&mut records);
+                print_assignments(&records, format, template.as_ref())?;
+            }
+        }
+
+        Commands::Exam { filter } => {
+            let app_config = APP_CONFIG.get().unwrap();
+            let client = client(app_config).await?;
+            let filter = AssignmentFilter::new(&filter)?;
+
+            if is_text {
+                exam(&client, &filter).await?;
+            } else {
+                let mut records = exam_records(&client, &filter).await?;
+                filter.query.sort(&mut records);
+                print_assignments(&records, format, template.as_ref())?;
+            }
+        }
+
+        Commands::Check { filter, agenda } => {
+            let app_config = APP_CONFIG.get().unwrap();
+            let client = client(app_config).await?;
+            let mut filter = AssignmentFilter::new(&filter)?;
+            if agenda {
+                filter.query.sort.get_or_insert(SortKey::Due);
+            }
+
+            let urgent = if !is_text {
+                let mut records = report_records(&client, &filter).await?;
+                records.extend(exam_records(&client, &filter).await?);
+                records.extend(todo_records(&filter)?);
+                filter.query.sort(&mut records);
+                print_assignments(&records, format, template.as_ref())?;
+                urgency::count_urgent(&records)
+            } else if agenda {
+                agenda::agenda(&client, &filter).await?
+            } else {
+                println!(
+                    "============ {} ============\n",
+                    " Report ".black().on_white()
+                );
+                let reports = report(&client, &filter).await?;
+
+                println!(
+                    "============ {} ============\n",
+                    " Exam ".black().on_white()
+                );
+                let exams = exam(&client, &filter).await?;
+
+                // Todos are shown only once there are some, unlike reports and exams
+                let todos = todo_records(&filter)?;
+                let urgent_todos = urgency::count_urgent(&todos);
+                if !todos.is_empty() {
+                    println!(
+                        "============ {} ============\n",
+                        " Todo ".black().on_white()
+                    );
+                    todo::todo(&filter, todos);
+                }
+
+                reports + exams + urgent_todos
+            };
+
+            if urgent > 0 {
+                return Ok(ExitCode::from(EXIT_URGENT));
+            }
+        }
+
+        Commands::Status {
+            json,
+            i3blocks,
+            max_age,
+            refresh,
+        } => {
+            return status(json, i3blocks, max_age, refresh).await;
+        }
+
+        Commands::Tui { interval } => {
             let app_config = APP_CONFIG.get().unwrap();
             let client = client(app_config).await?;
 
-            report::report(&client, all, warn).await?;
+            tui(client, interval).await?;
         }
 
-        Commands::Exam { all, warn } => {
+        Commands::Watch {
+            interval,
+            once,
+            dry_run,
+        } => {
             let app_config = APP_CONFIG.get().unwrap();
             let client = client(app_config).await?;
+            let notifier = Notifier::new(&app_config.notify, &app_config.base_url, dry_run);
 
-            exam(&client, all, warn).await?;
+            watch(
+                &client,
+                &notifier,
+                &app_config.notify.lead_hours,
+                interval,
+                once,
+            )
+            .await?;
         }
 
-        Commands::Check { all, warn } => {
+        Commands::Digest { smtp } => {
             let app_config = APP_CONFIG.get().unwrap();
+
+            let smtp = if smtp {
+                Some(
+                    app_config
+                        .smtp
+                        .as_ref()
+                        .ok_or(Error::MissingConfig("smtp"))?,
+                )
+            } else {
+                None
+            };
+
             let client = client(app_config).await?;
+            digest(&client, smtp).await?;
+        }
 
-            println!(
-                "============ {} ============\n",
-                " Report ".black().on_white()
-            );
-            report(&client, all, warn).await?;
+        Commands::ServeIcs { bind, interval } => {
+            let app_config = APP_CONFIG.get().unwrap();
+            let client = client(app_config).await?;
 
-            println!(
-                "============ {} ============\n",
-                " Exam ".black().on_white()
-            );
-            exam(&client, all, warn).await?;
+            serve_ics(client, bind, interval).await?;
         }
 
         Commands::Browse => {
@@ -96,23 +488,122 @@ pub async fn cmd() -> Result<()> {
             opener::open(&app_config.base_url)?;
         }
 
-        Commands::Course => {
+        Commands::Open { query, print } => {
             let app_config = APP_CONFIG.get().unwrap();
             let client = client(app_config).await?;
-            course(&client).await?;
+
+            let query = Some(query.join(" ")).filter(|query| !query.is_empty());
+            open(&client, query.as_deref(), print).await?;
+        }
+
+        Commands::Todo {
+            action: Some(action),
+        } => {
+            edit_todos(action).await?;
+        }
+
+        Commands::Todo { action: None } => {
+            list_todos(format)?;
+        }
+
+        Commands::Mark { id, status, note } => {
+            mark(&id, status, note)?;
+        }
+
+        Commands::Course {
+            action: Some(action),
+        } => {
+            edit_course_prefs(action).await?;
+        }
+
+        Commands::Course { action: None } => {
+            let app_config = APP_CONFIG.get().unwrap();
+            let client = client(app_config).await?;
+
+            if format == OutputFormat::Text {
+                course(&client).await?;
+            } else {
+                print_records(&course_records(&client).await?, format)?;
+            }
         }
 
         Commands::Timetable => {
             let app_config = APP_CONFIG.get().unwrap();
-            timetable(&app_config.timetable);
+            let client = client(app_config).await?;
+
+            if format == OutputFormat::Text {
+                timetable(&client, &app_config.timetable).await?;
+            } else {
+                print_records(
+                    &timetable_records(&client, &app_config.timetable).await?,
+                    format,
+                )?;
+            }
+        }
+
+        Commands::Today => {
+            let app_config = APP_CONFIG.get().unwrap();
+            let client = client(app_config).await?;
+
+            today(&client, &app_config.timetable, format).await?;
+        }
+
+        Commands::Next => {
+            let app_config = APP_CONFIG.get().unwrap();
+            let client = client(app_config).await?;
+
+            next(&client, &app_config.timetable, format).await?;
+        }
+
+        Commands::Completions { shell } => {
+            complete::completions(&mut Cli::command(), shell);
+        }
+
+        Commands::Man { dir } => {
+            complete::man(Cli::command(), dir.as_deref())?;
         }
 
         Commands::ConfigPath => {
             println!("{:?}", APP_CONFIG_PATH.get().unwrap());
         }
+
+        Commands::Config { action } => {
+            config(action)?;
+        }
+
+        Commands::Doctor => {
+            if !doctor().await {
+                return Ok(ExitCode::from(EXIT_FAILURE));
+            }
+        }
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+fn resolve_template(template: &str) -> Result<Template> {
+    let app_config = APP_CONFIG.get().unwrap();
+
+    match app_config.templates.get(template) {
+        Some(named) => Template::parse(named),
+        None => Template::parse(template),
+    }
+}
+
+fn print_assignments(
+    records: &[AssignmentRecord],
+    format: OutputFormat,
+    template: Option<&Template>,
+) -> Result<()> {
+    match template {
+        Some(template) => {
+            for record in records {
+                println!("{}", template.render(record));
+            }
+            Ok(())
+        }
+        None => print_records(records, format),
+    }
 }
 
 fn colorize<T: std::fmt::Display>(
@@ -128,7 +619,9 @@ fn colorize<T: std::fmt::Display>(
     }
 
     match importance_level {
-        AssignmentImportanceLevel::High => text.red(),
+        AssignmentImportanceLevel::High
+        | AssignmentImportanceLevel::DueToday
+        | AssignmentImportanceLevel::Overdue => text.red(),
         AssignmentImportanceLevel::Medium => text.yellow(),
         AssignmentImportanceLevel::Low => text.aqua(),
         AssignmentImportanceLevel::None => text.to_string(),
@@ -148,13 +641,47 @@ fn colorize_bg<T: std::fmt::Display>(
     }
 
     match importance_level {
-        AssignmentImportanceLevel::High => text.on_red(),
+        AssignmentImportanceLevel::High
+        | AssignmentImportanceLevel::DueToday
+        | AssignmentImportanceLevel::Overdue => text.on_red(),
         AssignmentImportanceLevel::Medium => text.on_yellow(),
         AssignmentImportanceLevel::Low => text.on_aqua(),
         AssignmentImportanceLevel::None => text.to_string(),
     }
 }
 
+fn state_label(state: AssignmentState) -> String {
+    match state {
+        AssignmentState::Todo => " TODO ".black().on_red(),
+        AssignmentState::Done => " DONE ".aqua().on_gray(),
+        AssignmentState::Closed => " CLOSED ".black().on_gray(),
+        AssignmentState::Waiting => " WAITING ".black().on_gray(),
+        AssignmentState::Ignored => " IGNORED ".black().on_gray(),
+    }
+}
+
+/// Status from `manaba mark`, shown after the title
+fn local_label(local: Option<&LocalState>) -> String {
+    let label = match local.and_then(|local| local.status) {
+        Some(LocalStatus::Started) => "started",
+        Some(LocalStatus::Ignored) => "ignored",
+        Some(LocalStatus::DoneOffline) => "done offline",
+        None => return String::new(),
+    };
+
+    format!(" [{label}]").gray()
+}
+
+fn note_line(local: Option<&LocalState>) -> String {
+    local
+        .and_then(|local| local.note.as_ref())
+        .map_or(String::new(), |note| {
+            format!("\n{INDENT}{INDENT}メモ: {note}")
+        })
+}
+
 fn date_as_str(report_date: &AssignmentDate) -> String {
-    report_date.date.format("%Y-%m-%d %H:%M").to_string()
+    timezone::to_display(&report_date.date)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
 }
@@ -0,0 +1,111 @@
+use crate::{
+    cmd::{
+        INDENT, colorize, exam_records, filter::AssignmentFilter, local_label, note_line,
+        report_records, state_label, timetable::weekday_label, todo_records,
+    },
+    color::AppColorize as _,
+    courses,
+    error::Result,
+    output::AssignmentRecord,
+    timezone, urgency,
+};
+use chrono::{Datelike as _, Duration, NaiveDate};
+use manaba_sdk::{
+    Client,
+    assignment::{AssignmentImportanceLevel, AssignmentKind},
+    overlay::LocalState,
+    query::Queryable as _,
+};
+
+/// Reports, exams and todos merged into one timeline, grouped by the day they are due
+///
+/// Returns how many of them are open and urgent.
+pub async fn agenda(client: &Client, filter: &AssignmentFilter) -> Result<usize> {
+    let mut records = report_records(client, filter).await?;
+    records.extend(exam_records(client, filter).await?);
+    records.extend(todo_records(filter)?);
+    // Days come in order, and `--sort` orders the assignments within each day
+    filter.query.sort(&mut records);
+    records.sort_by_key(|record| {
+        let day = record.due_date.map(|due_date| due_date.date_naive());
+        (day.is_none(), day)
+    });
+
+    if records.is_empty() {
+        println!("No assignments");
+        return Ok(0);
+    }
+
+    let today = timezone::now().date_naive();
+    let mut current_day = None;
+
+    for record in &records {
+        let day = record.due_date.map(|due_date| due_date.date_naive());
+        if current_day != Some(day) {
+            if current_day.is_some() {
+                println!();
+            }
+            println!("{}", day_header(day, today).black().on_blue().with_bold());
+            current_day = Some(day);
+        }
+
+        println!("{}", line(record, filter.shows_states()));
+    }
+
+    Ok(urgency::count_urgent(&records))
+}
+
+fn day_header(day: Option<NaiveDate>, today: NaiveDate) -> String {
+    let Some(day) = day else {
+        return " 期限なし ".to_owned();
+    };
+
+    let date = format!("{} ({})", day.format("%m/%d"), weekday_label(day.weekday()));
+    if day == today {
+        format!(" 今日 {date} ")
+    } else if day == today + Duration::days(1) {
+        format!(" 明日 {date} ")
+    } else {
+        format!(" {date} ")
+    }
+}
+
+fn line(record: &AssignmentRecord, shows_state: bool) -> String {
+    let time = record.due_date.map_or("--:--".to_owned(), |due_date| {
+        due_date.format("%H:%M").to_string()
+    });
+    let kind = match record.kind {
+        AssignmentKind::Report => "Report",
+        AssignmentKind::Exam => "Exam  ",
+        AssignmentKind::Todo => "Todo  ",
+    };
+    let content = colorize(
+        format!("{time}  {kind}  {}", record.title),
+        &record.receptible_state,
+        &record.importance.unwrap_or(AssignmentImportanceLevel::None),
+    );
+
+    let course = format!("({})", record.course_title);
+    let course = match courses::color(&record.course_id) {
+        // Todos without a course
+        _ if record.course_title.is_empty() => String::new(),
+        Some(hex) => colored_text::Colorize::hex(&course, &hex),
+        None => course.gray(),
+    };
+
+    let state = if shows_state {
+        state_label(record.state()) + " "
+    } else {
+        String::new()
+    };
+    let local = LocalState {
+        status: record.local_status,
+        note: record.note.clone(),
+    };
+
+    format!(
+        "{INDENT}{state}{content}{}  {course}{}",
+        local_label(Some(&local)),
+        note_line(Some(&local))
+    )
+}
@@ -0,0 +1,69 @@
+use crate::{cache, cmd::todo::todo_path, courses::strip_code, error::Result};
+use clap::Command;
+use clap_complete::{CompletionCandidate, Shell};
+use manaba_sdk::todo::TodoList;
+use std::path::Path;
+
+/// Static completion script for `shell`
+pub fn completions(command: &mut Command, shell: Shell) {
+    clap_complete::generate(shell, command, "manaba", &mut std::io::stdout());
+}
+
+/// Print the man page, or write one page per subcommand into `dir`
+pub fn man(command: Command, dir: Option<&Path>) -> Result<()> {
+    match dir {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)?;
+            clap_mangen::generate_to(command, dir)?;
+        }
+        None => clap_mangen::Man::new(command).render(&mut std::io::stdout())?,
+    }
+
+    Ok(())
+}
+
+// Dynamic candidates are listed before the config is loaded, so they only read the cache and
+// the todo list
+
+/// Course ids and titles from the cache
+pub fn course_candidates() -> Vec<CompletionCandidate> {
+    cache::cached_courses()
+        .into_iter()
+        .flat_map(|(id, title)| {
+            [
+                CompletionCandidate::new(&id).help(Some(title.clone().into())),
+                CompletionCandidate::new(strip_code(&title)).help(Some(id.into())),
+            ]
+        })
+        .collect()
+}
+
+/// Course titles without their code, which `manaba open` matches against
+pub fn title_candidates() -> Vec<CompletionCandidate> {
+    cache::cached_courses()
+        .into_iter()
+        .map(|(_, title)| CompletionCandidate::new(strip_code(&title)))
+        .collect()
+}
+
+/// Assignment ids from the cache and todo ids
+pub fn assignment_candidates() -> Vec<CompletionCandidate> {
+    cache::cached_assignments()
+        .into_iter()
+        .map(|assignment| {
+            CompletionCandidate::new(assignment.id).help(Some(
+                format!("{} ({})", assignment.title, assignment.course_title).into(),
+            ))
+        })
+        .chain(todo_candidates())
+        .collect()
+}
+
+pub fn todo_candidates() -> Vec<CompletionCandidate> {
+    let todos = TodoList::load(&todo_path()).unwrap_or_default();
+
+    todos
+        .iter()
+        .map(|todo| CompletionCandidate::new(&todo.id).help(Some(todo.title.clone().into())))
+        .collect()
+}
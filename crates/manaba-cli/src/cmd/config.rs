@@ -0,0 +1,169 @@
+use crate::{
+    APP_CONFIG, APP_CONFIG_PATH,
+    app_config::{self, AppConfig, edit_config_file, table_mut},
+    cmd::ConfigAction,
+    error::{Error, Result, print_err},
+};
+use std::{path::Path, process::Command};
+
+pub fn config(action: ConfigAction) -> Result<()> {
+    let path = APP_CONFIG_PATH.get().unwrap();
+
+    match action {
+        ConfigAction::Show => show(),
+        ConfigAction::Get { key } => get(&key),
+        ConfigAction::Set { key, value } => set(&key, &value),
+        ConfigAction::Edit => edit(path),
+        ConfigAction::Validate => validate(path),
+        ConfigAction::Init { force } => init(path, force),
+        ConfigAction::Path => {
+            println!("{}", path.display());
+            Ok(())
+        }
+    }
+}
+
+/// Print the config in effect, with defaults filled in
+fn show() -> Result<()> {
+    print!("{}", toml::to_string(&masked_config())?);
+    Ok(())
+}
+
+fn get(key: &str) -> Result<()> {
+    let mut value = &toml::Value::try_from(masked_config())?;
+
+    for segment in key_segments(key) {
+        value = value
+            .get(&segment)
+            .ok_or_else(|| Error::ConfigKeyNotFound(key.to_owned()))?;
+    }
+
+    match value {
+        toml::Value::String(s) => println!("{s}"),
+        toml::Value::Table(table) => print!("{}", toml::to_string(table)?),
+        value => println!("{value}"),
+    }
+
+    Ok(())
+}
+
+/// The config in effect, without the SMTP password
+fn masked_config() -> AppConfig {
+    let mut app_config = APP_CONFIG.get().unwrap().clone();
+    if let Some(smtp) = &mut app_config.smtp
+        && smtp.password.is_some()
+    {
+        smtp.password = Some("********".to_owned());
+    }
+
+    app_config
+}
+
+fn set(key: &str, value: &str) -> Result<()> {
+    let segments = key_segments(key);
+    let Some((last, parents)) = segments.split_last() else {
+        return Err(Error::ConfigKeyNotFound(key.to_owned()));
+    };
+
+    edit_config_file(|document| {
+        let before = app_config::validate(&document.to_string());
+
+        let mut table = document.as_table_mut();
+        for parent in parents {
+            table = table_mut(table, parent)?;
+        }
+
+        // Keep strings as strings, so that `set courses.x.alias 42` does not store a number
+        let is_string = table.get(last).is_some_and(|item| item.is_str());
+        let value = match value.parse::<toml_edit::Value>() {
+            Ok(parsed) if !is_string => parsed,
+            _ => value.into(),
+        };
+        table[last.as_str()] = toml_edit::value(value);
+
+        // Only refuse problems introduced by this change
+        let issues = app_config::validate(&document.to_string())
+            .into_iter()
+            .filter(|issue| !before.iter().any(|old| old.message == issue.message))
+            .collect::<Vec<_>>();
+        if !issues.is_empty() {
+            for issue in &issues {
+                print_err(&issue.message);
+            }
+            return Err(Error::InvalidConfig(issues.len()));
+        }
+
+        Ok(())
+    })?;
+
+    println!("Set {key} to {value}");
+    Ok(())
+}
+
+/// Open the config file with `$VISUAL` or `$EDITOR`, then check it
+fn edit(path: &Path) -> Result<()> {
+    if !path.exists() {
+        app_config::write_default_config(path)?;
+    }
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_owned());
+    let mut words = editor.split_whitespace();
+
+    let status = Command::new(words.next().unwrap_or("vi"))
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|source| Error::Editor {
+            command: editor.clone(),
+            source,
+        })?;
+
+    if status.success() {
+        validate(path)
+    } else {
+        Ok(())
+    }
+}
+
+fn validate(path: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(path)?;
+    let issues = app_config::validate(&content);
+
+    if issues.is_empty() {
+        println!("{} is valid", path.display());
+        return Ok(());
+    }
+
+    for issue in &issues {
+        print_err(format!("{}:{issue}", path.display()));
+    }
+    Err(Error::InvalidConfig(issues.len()))
+}
+
+fn init(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        return Err(Error::ConfigExists(path.to_owned()));
+    }
+
+    app_config::write_default_config(path)?;
+    println!("Config file created at {}", path.display());
+    Ok(())
+}
+
+/// Split `courses."course_12345".color` into its keys
+fn key_segments(key: &str) -> Vec<String> {
+    let mut segments = vec![String::new()];
+    let mut quoted = false;
+
+    for c in key.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            '.' if !quoted => segments.push(String::new()),
+            c => segments.last_mut().unwrap().push(c),
+        }
+    }
+
+    segments
+}
@@ -1,11 +1,23 @@
-use crate::{color::AppColorize as _, error::Result};
-use dialoguer::{theme::ColorfulTheme, Select};
+use crate::{
+    cache,
+    color::AppColorize as _,
+    courses,
+    error::Result,
+    output::CourseRecord,
+    overlay,
+    picker::{self, PickItem},
+    urgency,
+};
 use manaba_sdk::{
     Client, Course,
-    assignment::{AssignmentImportanceLevel, AssignmentReceptibleState, AssignmentSubmitState},
+    assignment::AssignmentImportanceLevel,
+    query::{AssignmentState, Queryable as _},
 };
 
-async fn count_assignments_by_urgency(client: &Client, course: &Course) -> Result<(usize, usize, usize)> {
+async fn count_assignments_by_urgency(
+    client: &Client,
+    course: &Course,
+) -> Result<(usize, usize, usize)> {
     let mut high_count = 0;
     let mut medium_count = 0;
     let mut low_count = 0;
@@ -13,21 +25,16 @@ async fn count_assignments_by_urgency(client: &Client, course: &Course) -> Resul
     // Count reports
     let reports = client.get_reports(course).await?;
     for report in reports {
-        if matches!(
-            report,
-            manaba_sdk::Report {
-                submit_state: AssignmentSubmitState::Todo,
-                receptiable_state: AssignmentReceptibleState::Open,
-                ..
-            }
-        ) {
-            if let Some(due_date) = &report.due_date {
-                match due_date.importance_level {
-                    AssignmentImportanceLevel::High => high_count += 1,
-                    AssignmentImportanceLevel::Medium => medium_count += 1,
-                    AssignmentImportanceLevel::Low => low_count += 1,
-                    AssignmentImportanceLevel::None => {},
-                }
+        if overlay::overlay().wrap((course, &report)).state() == AssignmentState::Todo
+            && let Some(due_date) = &report.due_date
+        {
+            match urgency::importance_level(&due_date.date) {
+                AssignmentImportanceLevel::High
+                | AssignmentImportanceLevel::DueToday
+                | AssignmentImportanceLevel::Overdue => high_count += 1,
+                AssignmentImportanceLevel::Medium => medium_count += 1,
+                AssignmentImportanceLevel::Low => low_count += 1,
+                AssignmentImportanceLevel::None => {}
             }
         }
     }
@@ -35,21 +42,16 @@ async fn count_assignments_by_urgency(client: &Client, course: &Course) -> Resul
     // Count exams
     let exams = client.get_exams(course).await?;
     for exam in exams {
-        if matches!(
-            exam,
-            manaba_sdk::Exam {
-                submit_state: AssignmentSubmitState::Todo,
-                receptiable_state: AssignmentReceptibleState::Open,
-                ..
-            }
-        ) {
-            if let Some(due_date) = &exam.due_date {
-                match due_date.importance_level {
-                    AssignmentImportanceLevel::High => high_count += 1,
-                    AssignmentImportanceLevel::Medium => medium_count += 1,
-                    AssignmentImportanceLevel::Low => low_count += 1,
-                    AssignmentImportanceLevel::None => {},
-                }
+        if overlay::overlay().wrap((course, &exam)).state() == AssignmentState::Todo
+            && let Some(due_date) = &exam.due_date
+        {
+            match urgency::importance_level(&due_date.date) {
+                AssignmentImportanceLevel::High
+                | AssignmentImportanceLevel::DueToday
+                | AssignmentImportanceLevel::Overdue => high_count += 1,
+                AssignmentImportanceLevel::Medium => medium_count += 1,
+                AssignmentImportanceLevel::Low => low_count += 1,
+                AssignmentImportanceLevel::None => {}
             }
         }
     }
@@ -58,62 +60,129 @@ async fn count_assignments_by_urgency(client: &Client, course: &Course) -> Resul
 }
 
 fn format_assignment_indicator(high: usize, medium: usize, low: usize) -> String {
-    let high_str = if high > 0 { high.to_string().red() } else { "0".gray() };
-    let medium_str = if medium > 0 { medium.to_string().yellow() } else { "0".gray() };
-    let low_str = if low > 0 { low.to_string().aqua() } else { "0".gray() };
-    
+    let high_str = if high > 0 {
+        high.to_string().red()
+    } else {
+        "0".gray()
+    };
+    let medium_str = if medium > 0 {
+        medium.to_string().yellow()
+    } else {
+        "0".gray()
+    };
+    let low_str = if low > 0 {
+        low.to_string().aqua()
+    } else {
+        "0".gray()
+    };
+
     format!("({}/{}/{}) ", high_str, medium_str, low_str)
 }
 
+pub async fn course_records(client: &Client) -> Result<Vec<CourseRecord>> {
+    let courses = courses::courses(client).await?;
+    let mut records = Vec::new();
+
+    for course in courses {
+        let (high, medium, low) = count_assignments_by_urgency(client, &course).await?;
+        records.push(CourseRecord {
+            url: course.url(&client.base_url),
+            id: course.id,
+            title: course.title,
+            year: course.year,
+            term: course.term,
+            schedule: course.schedule,
+            instructors: course.instructors.join(", "),
+            status: course.status,
+            high,
+            medium,
+            low,
+        });
+    }
+
+    Ok(records)
+}
+
 pub async fn course(client: &Client) -> Result<()> {
-    let courses = client.get_courses().await?;
-    
+    let courses = courses::courses(client).await?;
+
     if courses.is_empty() {
         println!("No courses found.");
         return Ok(());
     }
 
     println!("{}", "Loading assignment information...".gray());
-    
+
+    let ids = courses
+        .iter()
+        .map(|course| course.id.as_str())
+        .collect::<Vec<_>>();
+    let pages = cache::page_texts(client, &ids).await;
+
     // Collect assignment counts for each course
-    let mut course_display_items = Vec::new();
+    let mut items = Vec::new();
     for course in &courses {
         let (high, medium, low) = count_assignments_by_urgency(client, course).await?;
         let indicator = format_assignment_indicator(high, medium, low);
-        let display_title = format!("{}{}", indicator, course.title);
-        course_display_items.push(display_title);
+        items.push(PickItem {
+            label: format!("{}{}", indicator, courses::colored_title(course)),
+            preview: course_preview(
+                course,
+                &client.base_url,
+                pages.get(&course.id).map(Vec::as_slice),
+            ),
+        });
     }
-    
-    let total_courses = courses.len();
-    let max_display = 10;
-    
-    let prompt = "Select a course to open";
-    
-    // Display course count information
-    if total_courses > max_display {
-        println!("{}", format!("Found {} courses (showing {} at a time)", total_courses, max_display).to_string().aqua());
-        println!("{}", "Use ↑↓ to navigate, PgUp/PgDn for pages, Enter to select".to_string().gray());
-        println!("{}", "Assignment counts: (urgent/1week/normal)".to_string().gray());
-        println!();
-    } else {
-        println!("{}", format!("Found {} courses", total_courses).to_string().aqua());
-        println!("{}", "Use ↑↓ to navigate, Enter to select".to_string().gray());
-        println!("{}", "Assignment counts: (urgent/1week/normal)".to_string().gray());
-        println!();
-    }
-    
-    let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt(prompt)
-        .default(0)
-        .max_length(max_display)
-        .items(&course_display_items)
-        .interact()?;
+
+    println!(
+        "{}",
+        format!("Found {} courses", courses.len())
+            .to_string()
+            .aqua()
+    );
+    println!(
+        "{}",
+        "Type to filter, ↑↓ to navigate, Enter to select"
+            .to_string()
+            .gray()
+    );
+    println!(
+        "{}",
+        format!(
+            "Assignment counts: (urgent/within {}/later)",
+            urgency::medium_reach()
+        )
+        .gray()
+    );
+    println!();
+
+    let Some(selection) = picker::pick("Select a course to open", &items)? else {
+        return Ok(());
+    };
 
     let selected_course = &courses[selection];
     let course_url = selected_course.url(&client.base_url);
-    
+
     println!("Opening course: {}", selected_course.title.green());
     opener::open(&course_url)?;
-    
+
     Ok(())
-}
\ No newline at end of file
+}
+
+fn course_preview(course: &Course, base_url: &str, page: Option<&[String]>) -> String {
+    let mut lines = vec![course.title.clone()];
+
+    if !course.instructors.is_empty() {
+        lines.push(format!("担当: {}", course.instructors.join(", ")));
+    }
+    if let Some(schedule) = &course.schedule {
+        lines.push(format!("曜日・時限: {schedule}"));
+    }
+    if let Some(status) = &course.status {
+        lines.push(format!("状態: {status}"));
+    }
+    lines.push(course.url(base_url));
+    picker::push_page(&mut lines, page);
+
+    lines.join("\n")
+}
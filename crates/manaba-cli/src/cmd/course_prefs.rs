@@ -0,0 +1,85 @@
+use crate::{
+    APP_CONFIG,
+    app_config::{edit_config_file, table_mut},
+    cache, client,
+    cmd::CourseAction,
+    courses::normalize_id,
+    error::{Error, Result},
+};
+use toml_edit::value;
+
+pub async fn edit_course_prefs(action: CourseAction) -> Result<()> {
+    let (course, message) = match &action {
+        CourseAction::Hide { course } => (course, "Hid"),
+        CourseAction::Unhide { course } => (course, "Unhid"),
+        CourseAction::Pin { course } => (course, "Pinned"),
+        CourseAction::Unpin { course } => (course, "Unpinned"),
+        CourseAction::Alias {
+            course,
+            alias: Some(_),
+        } => (course, "Set alias of"),
+        CourseAction::Alias {
+            course,
+            alias: None,
+        } => (course, "Removed alias of"),
+    };
+    let course_id = known_course_id(course).await?;
+
+    edit_config_file(|document| {
+        let courses = table_mut(document.as_table_mut(), "courses")?;
+        courses.set_implicit(true);
+
+        let prefs = table_mut(courses, &course_id)?;
+
+        match action {
+            CourseAction::Hide { .. } => prefs["hidden"] = value(true),
+            CourseAction::Pin { .. } => prefs["pinned"] = value(true),
+            CourseAction::Alias {
+                alias: Some(alias), ..
+            } => prefs["alias"] = value(alias),
+            CourseAction::Unhide { .. } => {
+                prefs.remove("hidden");
+            }
+            CourseAction::Unpin { .. } => {
+                prefs.remove("pinned");
+            }
+            CourseAction::Alias { alias: None, .. } => {
+                prefs.remove("alias");
+            }
+        }
+
+        if prefs.is_empty() {
+            courses.remove(&course_id);
+        }
+
+        Ok(())
+    })?;
+
+    println!("{message} {course_id}");
+    Ok(())
+}
+
+/// Resolve `course` to the id of a course that exists, so that typos are not saved
+async fn known_course_id(course: &str) -> Result<String> {
+    let app_config = APP_CONFIG.get().unwrap();
+    let course_id = normalize_id(course);
+
+    // Courses with preferences may be gone from manaba, but can still be cleaned up
+    if app_config.courses.contains_key(&course_id)
+        || cache::cached_courses()
+            .iter()
+            .any(|(id, _)| *id == course_id)
+    {
+        return Ok(course_id);
+    }
+
+    // The cache leaves out hidden courses and courses of other terms, so ask manaba
+    let client = client(app_config).await?;
+    let courses = client.get_courses().await?;
+
+    if courses.iter().any(|course| course.id == course_id) {
+        Ok(course_id)
+    } else {
+        Err(Error::CourseNotFound(course.to_owned()))
+    }
+}
@@ -0,0 +1,614 @@
+use crate::{
+    app_config::{SmtpConfig, SmtpTls},
+    cache, courses,
+    error::{Error, Result, print_err},
+    output::AssignmentRecord,
+    timezone, urgency,
+};
+use chrono::{DateTime, FixedOffset, Utc};
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport as _, Message, Tokio1Executor,
+    message::{Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+};
+use manaba_sdk::{
+    Client,
+    assignment::AssignmentImportanceLevel,
+    query::{AssignmentState, Queryable as _},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Write as _, path::PathBuf};
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// What the previous digest contained, to find news and deadline changes since then
+#[derive(Serialize, Deserialize, Default)]
+struct DigestState {
+    /// Unix timestamp of the last sent digest
+    sent_at: Option<i64>,
+    due_dates: BTreeMap<String, Option<DateTime<FixedOffset>>>,
+}
+
+impl DigestState {
+    fn path() -> Result<PathBuf> {
+        let data_dir_path = dirs::data_dir().ok_or(Error::DataDirNotFound)?;
+        Ok(data_dir_path.join("manaba").join("digest_state.json"))
+    }
+
+    fn load() -> Result<Self> {
+        Ok(std::fs::read_to_string(Self::path()?)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default())
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+struct NewsItem {
+    course_title: String,
+    title: String,
+    url: String,
+    posted_at: Option<DateTime<FixedOffset>>,
+}
+
+struct DeadlineChange {
+    assignment: AssignmentRecord,
+    previous: Option<DateTime<FixedOffset>>,
+}
+
+struct Digest {
+    groups: Vec<(String, Vec<AssignmentRecord>)>,
+    news: Vec<NewsItem>,
+    changes: Vec<DeadlineChange>,
+}
+
+pub async fn digest(client: &Client, smtp: Option<&SmtpConfig>) -> Result<()> {
+    let mut state = DigestState::load()?;
+
+    let since = state
+        .sent_at
+        .and_then(|sent_at| DateTime::from_timestamp(sent_at, 0))
+        .unwrap_or_else(|| Utc::now() - chrono::Duration::days(1));
+
+    let assignments = cache::refresh(client).await?;
+
+    let mut news = Vec::new();
+    for course in courses::courses(client).await? {
+        // One unreachable course should not hold back the whole digest
+        let items = match client.get_news(&course).await {
+            Ok(items) => items,
+            Err(e) => {
+                print_err(format!("Skipped news of {}: {e}", course.title));
+                continue;
+            }
+        };
+
+        for item in items {
+            if item.posted_at.is_some_and(|posted_at| posted_at > since) {
+                news.push(NewsItem {
+                    course_title: course.title.clone(),
+                    url: item.url(&client.base_url),
+                    title: item.title,
+                    posted_at: item.posted_at.map(|d| timezone::to_display(&d)),
+                });
+            }
+        }
+    }
+    news.sort_by_key(|item| item.posted_at);
+
+    let digest = Digest {
+        groups: group_by_importance(&assignments),
+        news,
+        changes: deadline_changes(&state, &assignments),
+    };
+
+    let Some(smtp) = smtp else {
+        print!("{}", render_text(&digest, &client.base_url));
+        return Ok(());
+    };
+
+    send(smtp, &digest, &client.base_url).await?;
+    println!("Digest sent to {}", smtp.to.join(", "));
+
+    state.sent_at = Some(Utc::now().timestamp());
+    state.due_dates = assignments
+        .iter()
+        .map(|assignment| (assignment.id.clone(), assignment.due_date))
+        .collect();
+    state.save()
+}
+
+/// Assignments whose deadline differs from the one in the previous digest
+fn deadline_changes(state: &DigestState, assignments: &[AssignmentRecord]) -> Vec<DeadlineChange> {
+    assignments
+        .iter()
+        .filter_map(|assignment| {
+            let previous = *state.due_dates.get(&assignment.id)?;
+            (previous != assignment.due_date).then(|| DeadlineChange {
+                assignment: assignment.clone(),
+                previous,
+            })
+        })
+        .collect()
+}
+
+fn group_by_importance(assignments: &[AssignmentRecord]) -> Vec<(String, Vec<AssignmentRecord>)> {
+    let mut open_assignments = assignments
+        .iter()
+        .filter(|assignment| assignment.state() == AssignmentState::Todo)
+        .cloned()
+        .collect::<Vec<_>>();
+    open_assignments.sort_by_key(|assignment| assignment.due_date);
+
+    [
+        (
+            "Overdue".to_owned(),
+            Some(AssignmentImportanceLevel::Overdue),
+        ),
+        (
+            "Due today".to_owned(),
+            Some(AssignmentImportanceLevel::DueToday),
+        ),
+        ("Urgent".to_owned(), Some(AssignmentImportanceLevel::High)),
+        (
+            format!("Within {}", urgency::medium_reach()),
+            Some(AssignmentImportanceLevel::Medium),
+        ),
+        ("Later".to_owned(), Some(AssignmentImportanceLevel::Low)),
+        ("No deadline".to_owned(), None),
+    ]
+    .into_iter()
+    .map(|(label, importance)| {
+        let group = open_assignments
+            .iter()
+            .filter(|assignment| assignment.importance == importance)
+            .cloned()
+            .collect();
+        (label, group)
+    })
+    .collect()
+}
+
+fn format_date(date: Option<DateTime<FixedOffset>>) -> String {
+    date.map_or("-".to_owned(), |date| date.format(DATE_FORMAT).to_string())
+}
+
+fn render_text(digest: &Digest, base_url: &str) -> String {
+    let mut s = String::new();
+
+    for (label, assignments) in &digest.groups {
+        if assignments.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(s, "== {label} ({}) ==", assignments.len());
+        for assignment in assignments {
+            let _ = writeln!(
+                s,
+                "- {} {} [{}]\n  {}",
+                format_date(assignment.due_date),
+                assignment.title,
+                assignment.course_title,
+                assignment.url(base_url)
+            );
+        }
+        s.push('\n');
+    }
+
+    if !digest.changes.is_empty() {
+        let _ = writeln!(s, "== Changed deadlines ({}) ==", digest.changes.len());
+        for change in &digest.changes {
+            let _ = writeln!(
+                s,
+                "- {} [{}]: {} -> {}",
+                change.assignment.title,
+                change.assignment.course_title,
+                format_date(change.previous),
+                format_date(change.assignment.due_date)
+            );
+        }
+        s.push('\n');
+    }
+
+    if !digest.news.is_empty() {
+        let _ = writeln!(s, "== Course news ({}) ==", digest.news.len());
+        for item in &digest.news {
+            let _ = writeln!(
+                s,
+                "- {} {} [{}]\n  {}",
+                format_date(item.posted_at),
+                item.title,
+                item.course_title,
+                item.url
+            );
+        }
+        s.push('\n');
+    }
+
+    if s.is_empty() {
+        s.push_str("Nothing to do.\n");
+    }
+
+    s
+}
+
+fn render_html(digest: &Digest, base_url: &str) -> String {
+    let mut s = String::from("<html><body>");
+
+    for (label, assignments) in &digest.groups {
+        if assignments.is_empty() {
+            continue;
+        }
+
+        let _ = write!(s, "<h2>{label} ({})</h2><ul>", assignments.len());
+        for assignment in assignments {
+            let _ = write!(
+                s,
+                "<li>{} <a href=\"{}\">{}</a> [{}]</li>",
+                format_date(assignment.due_date),
+                escape_html(&assignment.url(base_url)),
+                escape_html(&assignment.title),
+                escape_html(&assignment.course_title)
+            );
+        }
+        s.push_str("</ul>");
+    }
+
+    if !digest.changes.is_empty() {
+        let _ = write!(
+            s,
+            "<h2>Changed deadlines ({})</h2><ul>",
+            digest.changes.len()
+        );
+        for change in &digest.changes {
+            let _ = write!(
+                s,
+                "<li>{} [{}]: <s>{}</s> &rarr; {}</li>",
+                escape_html(&change.assignment.title),
+                escape_html(&change.assignment.course_title),
+                format_date(change.previous),
+                format_date(change.assignment.due_date)
+            );
+        }
+        s.push_str("</ul>");
+    }
+
+    if !digest.news.is_empty() {
+        let _ = write!(s, "<h2>Course news ({})</h2><ul>", digest.news.len());
+        for item in &digest.news {
+            let _ = write!(
+                s,
+                "<li>{} <a href=\"{}\">{}</a> [{}]</li>",
+                format_date(item.posted_at),
+                escape_html(&item.url),
+                escape_html(&item.title),
+                escape_html(&item.course_title)
+            );
+        }
+        s.push_str("</ul>");
+    }
+
+    s.push_str("</body></html>");
+    s
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+async fn send(smtp: &SmtpConfig, digest: &Digest, base_url: &str) -> Result<()> {
+    let mut builder = Message::builder()
+        .from(smtp.from.parse::<Mailbox>()?)
+        .subject(format!(
+            "manaba digest {}",
+            timezone::now().format("%Y-%m-%d")
+        ));
+
+    for to in &smtp.to {
+        builder = builder.to(to.parse::<Mailbox>()?);
+    }
+
+    let message = builder.multipart(MultiPart::alternative_plain_html(
+        render_text(digest, base_url),
+        render_html(digest, base_url),
+    ))?;
+
+    let mut transport = match smtp.tls {
+        SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&smtp.host),
+        SmtpTls::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host)?,
+        SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp.host)?,
+    }
+    .port(smtp.port);
+
+    if let Some(username) = &smtp.username {
+        transport = transport.credentials(Credentials::new(
+            username.clone(),
+            smtp.password.clone().unwrap_or_default(),
+        ));
+    }
+
+    transport.build().send(message).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::APP_CONFIG;
+    use chrono::TimeZone as _;
+    use manaba_sdk::assignment::{
+        AssignmentKind, AssignmentReceptibleState, AssignmentSubmitState,
+    };
+    use tokio::{
+        io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+
+    const BASE_URL: &str = "https://manaba.example.com/ct";
+
+    fn date(day: u32) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(9 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2026, 10, day, 23, 59, 0)
+            .unwrap()
+    }
+
+    fn assignment(
+        id: &str,
+        due_date: Option<DateTime<FixedOffset>>,
+        importance: Option<AssignmentImportanceLevel>,
+    ) -> AssignmentRecord {
+        AssignmentRecord {
+            course_id: "course_11111".to_owned(),
+            course_title: "11111:Operating Systems".to_owned(),
+            kind: AssignmentKind::Report,
+            id: id.to_owned(),
+            title: "Essay".to_owned(),
+            submit_state: AssignmentSubmitState::Todo,
+            receptible_state: AssignmentReceptibleState::Open,
+            start_date: None,
+            due_date,
+            importance,
+            local_status: None,
+            note: None,
+        }
+    }
+
+    fn empty_digest() -> Digest {
+        Digest {
+            groups: Vec::new(),
+            news: Vec::new(),
+            changes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn group_by_importance_test() {
+        APP_CONFIG.get_or_init(Default::default);
+        let mut submitted = assignment(
+            "report_6",
+            Some(date(21)),
+            Some(AssignmentImportanceLevel::High),
+        );
+        submitted.submit_state = AssignmentSubmitState::Done;
+
+        let assignments = [
+            assignment(
+                "report_1",
+                Some(date(30)),
+                Some(AssignmentImportanceLevel::Low),
+            ),
+            assignment("report_2", None, None),
+            assignment(
+                "report_3",
+                Some(date(22)),
+                Some(AssignmentImportanceLevel::High),
+            ),
+            assignment(
+                "report_4",
+                Some(date(21)),
+                Some(AssignmentImportanceLevel::High),
+            ),
+            assignment(
+                "report_5",
+                Some(date(25)),
+                Some(AssignmentImportanceLevel::Medium),
+            ),
+            submitted,
+        ];
+
+        let groups = group_by_importance(&assignments);
+        let groups = groups
+            .iter()
+            .map(|(label, group)| {
+                let ids = group.iter().map(|a| a.id.as_str()).collect::<Vec<_>>();
+                (label.as_str(), ids)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            groups,
+            [
+                ("Overdue", vec![]),
+                ("Due today", vec![]),
+                ("Urgent", vec!["report_4", "report_3"]),
+                ("Within 7 days", vec!["report_5"]),
+                ("Later", vec!["report_1"]),
+                ("No deadline", vec!["report_2"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn deadline_changes_test() {
+        let state = DigestState {
+            sent_at: None,
+            due_dates: BTreeMap::from([
+                ("report_1".to_owned(), Some(date(20))),
+                ("report_2".to_owned(), Some(date(21))),
+                ("report_3".to_owned(), None),
+            ]),
+        };
+        let assignments = [
+            // Extended
+            assignment("report_1", Some(date(27)), None),
+            // Unchanged
+            assignment("report_2", Some(date(21)), None),
+            // Deadline added
+            assignment("report_3", Some(date(22)), None),
+            // Not in the previous digest
+            assignment("report_4", Some(date(23)), None),
+        ];
+
+        let changes = deadline_changes(&state, &assignments)
+            .into_iter()
+            .map(|change| {
+                (
+                    change.assignment.id,
+                    change.previous,
+                    change.assignment.due_date,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            changes,
+            [
+                ("report_1".to_owned(), Some(date(20)), Some(date(27))),
+                ("report_3".to_owned(), None, Some(date(22))),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_html_escape_test() {
+        let mut essay = assignment(
+            "report_1",
+            Some(date(21)),
+            Some(AssignmentImportanceLevel::High),
+        );
+        essay.title = "<b>Essay</b> & \"notes\"".to_owned();
+
+        let digest = Digest {
+            groups: vec![("Urgent".to_owned(), vec![essay.clone()])],
+            news: vec![NewsItem {
+                course_title: "<Networks>".to_owned(),
+                title: "Room change".to_owned(),
+                url: format!("{BASE_URL}/news?a=1&b=2"),
+                posted_at: None,
+            }],
+            changes: vec![DeadlineChange {
+                assignment: essay,
+                previous: None,
+            }],
+        };
+
+        let html = render_html(&digest, BASE_URL);
+
+        assert!(!html.contains("<b>Essay</b>"));
+        assert!(!html.contains("<Networks>"));
+        assert_eq!(
+            html.matches("&lt;b&gt;Essay&lt;/b&gt; &amp; &quot;notes&quot;")
+                .count(),
+            2
+        );
+        assert!(html.contains("[&lt;Networks&gt;]"));
+        assert!(html.contains("href=\"https://manaba.example.com/ct/news?a=1&amp;b=2\""));
+    }
+
+    #[test]
+    fn render_text_empty_test() {
+        assert_eq!(render_text(&empty_digest(), BASE_URL), "Nothing to do.\n");
+    }
+
+    /// Accept one SMTP session, replying OK to every command, and return the message data
+    async fn smtp_server() -> (u16, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+
+            let mut data = String::new();
+            let mut in_data = false;
+            while let Ok(Some(line)) = lines.next_line().await {
+                let reply: &[u8] = if in_data {
+                    if line != "." {
+                        data.push_str(&line);
+                        data.push('\n');
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 OK\r\n"
+                } else if line.eq_ignore_ascii_case("DATA") {
+                    in_data = true;
+                    b"354 Go ahead\r\n"
+                } else if line.eq_ignore_ascii_case("QUIT") {
+                    writer.write_all(b"221 Bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                writer.write_all(reply).await.unwrap();
+            }
+            data
+        });
+
+        (port, handle)
+    }
+
+    #[tokio::test]
+    async fn send_test() {
+        APP_CONFIG.get_or_init(Default::default);
+        let (port, handle) = smtp_server().await;
+
+        let smtp = SmtpConfig {
+            host: "127.0.0.1".to_owned(),
+            port,
+            tls: SmtpTls::None,
+            username: None,
+            password: None,
+            from: "manaba <manaba@example.com>".to_owned(),
+            to: vec!["student@example.com".to_owned()],
+        };
+        let digest = Digest {
+            groups: vec![(
+                "Urgent".to_owned(),
+                vec![assignment(
+                    "report_1",
+                    Some(date(21)),
+                    Some(AssignmentImportanceLevel::High),
+                )],
+            )],
+            ..empty_digest()
+        };
+
+        send(&smtp, &digest, BASE_URL).await.unwrap();
+        let data = handle.await.unwrap();
+
+        assert!(data.contains("From: manaba <manaba@example.com>"));
+        assert!(data.contains("To: student@example.com"));
+        assert!(data.contains("Subject: manaba digest "));
+        assert!(data.contains("Content-Type: text/plain"));
+        assert!(data.contains("Content-Type: text/html"));
+        assert!(data.contains("== Urgent (1) =="));
+    }
+}
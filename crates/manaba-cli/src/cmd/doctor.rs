@@ -0,0 +1,245 @@
+use crate::{
+    APP_CONFIG, APP_CONFIG_PATH, app_config, cmd::INDENT, color::AppColorize as _, is_interactive,
+};
+use manaba_sdk::{Client, Cookie, error::ManabaError};
+use std::{
+    fmt::Display,
+    io::IsTerminal as _,
+    time::{Duration, Instant},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Prints the result of each check and counts the failures
+#[derive(Default)]
+struct Report {
+    failures: usize,
+}
+
+impl Report {
+    fn section(&self, title: &str) {
+        println!("{}", title.with_bold());
+    }
+
+    fn ok(&self, message: impl Display) {
+        println!("{INDENT}{} {message}", "✔".green());
+    }
+
+    fn info(&self, message: impl Display) {
+        println!("{INDENT}{} {message}", "-".gray());
+    }
+
+    fn warn(&self, message: impl Display) {
+        println!("{INDENT}{} {message}", "!".yellow());
+    }
+
+    fn fail(&mut self, message: impl Display) {
+        println!("{INDENT}{} {message}", "✘".red());
+        self.failures += 1;
+    }
+}
+
+/// Check the config file, the connection, browser cookies, the scrapers and the terminal
+///
+/// Returns whether every check passed.
+pub async fn doctor() -> bool {
+    let mut report = Report::default();
+
+    config(&mut report);
+    println!();
+    connection(&mut report).await;
+    println!();
+    let client = cookies(&mut report).await;
+    println!();
+    scrapers(&mut report, client.as_ref()).await;
+    println!();
+    terminal(&mut report);
+    println!();
+
+    if report.failures == 0 {
+        println!("No problems found");
+    } else {
+        println!("{} problem(s) found", report.failures);
+    }
+
+    report.failures == 0
+}
+
+fn config(report: &mut Report) {
+    let path = APP_CONFIG_PATH.get().unwrap();
+    let app_config = APP_CONFIG.get().unwrap();
+    report.section("Config");
+
+    match std::fs::read_to_string(path) {
+        Ok(content) => {
+            let issues = app_config::validate(&content);
+            if issues.is_empty() {
+                report.ok(format!("{} is valid", path.display()));
+            } else {
+                report.fail(format!(
+                    "{} has {} problem(s), see `manaba config validate`",
+                    path.display(),
+                    issues.len()
+                ));
+                for issue in issues {
+                    println!("{INDENT}{INDENT}{issue}");
+                }
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            report.warn(format!(
+                "{} not found, using the defaults. Create it with `manaba config init`",
+                path.display()
+            ));
+        }
+        Err(e) => report.fail(format!("Cannot read {}: {e}", path.display())),
+    }
+
+    report.info(format!("base_url: {}", app_config.base_url));
+    report.info(format!("cookie_domain: {}", app_config.cookie_domain));
+}
+
+async fn connection(report: &mut Report) {
+    let app_config = APP_CONFIG.get().unwrap();
+    report.section("Connection");
+
+    let client = reqwest::Client::builder().timeout(TIMEOUT).build().unwrap();
+    let start = Instant::now();
+
+    match client.get(&app_config.base_url).send().await {
+        Ok(response) if response.status().is_server_error() => report.fail(format!(
+            "{} responded with {}",
+            app_config.base_url,
+            response.status()
+        )),
+        Ok(response) => report.ok(format!(
+            "{} responded with {} in {} ms",
+            app_config.base_url,
+            response.status(),
+            start.elapsed().as_millis()
+        )),
+        Err(e) => report.fail(format!("Cannot reach {}: {e}", app_config.base_url)),
+    }
+}
+
+/// Check the cookies of every browser, and return a client for the first valid one
+async fn cookies(report: &mut Report) -> Option<Client> {
+    let app_config = APP_CONFIG.get().unwrap();
+    let domain = &app_config.cookie_domain;
+    report.section("Cookies");
+
+    let browsers = Cookie::probe(domain);
+    // `Cookie::load` uses the first browser with a sessionid, even when manaba rejects it
+    let used = browsers.iter().position(|browser| browser.cookie.is_some());
+    let mut valid_client = None;
+
+    for (index, browser) in browsers.into_iter().enumerate() {
+        let name = browser.browser;
+
+        if let Some(error) = browser.error {
+            report.info(format!("{name}: cannot be read ({error})"));
+            continue;
+        }
+
+        let Some(cookie) = browser.cookie else {
+            if browser.count == 0 {
+                report.info(format!("{name}: no cookies for {domain}"));
+            } else {
+                report.warn(format!(
+                    "{name}: {} cookie(s) for {domain} but no sessionid, log in to manaba in {name}",
+                    browser.count
+                ));
+            }
+            continue;
+        };
+
+        let used_label = if used == Some(index) { " (used)" } else { "" };
+        match Client::new(&app_config.base_url, &cookie).await {
+            Ok(client) => {
+                report.ok(format!("{name}: sessionid is valid{used_label}"));
+                valid_client.get_or_insert(client);
+            }
+            Err(ManabaError::InvalidCookie) if used == Some(index) => report.fail(format!(
+                "{name}: sessionid is rejected by manaba, log in again in {name}{used_label}"
+            )),
+            Err(ManabaError::InvalidCookie) => {
+                report.warn(format!("{name}: sessionid is rejected by manaba"));
+            }
+            Err(e) => report.fail(format!("{name}: cannot check the sessionid: {e}")),
+        }
+    }
+
+    if used.is_none() {
+        report.fail(format!(
+            "No browser has a sessionid for {domain}, log in to manaba in Chrome, Chromium, Zen, Brave or Arc"
+        ));
+    }
+
+    valid_client
+}
+
+async fn scrapers(report: &mut Report, client: Option<&Client>) {
+    report.section("Scrapers");
+
+    let Some(client) = client else {
+        report.info("Skipped without a valid sessionid");
+        return;
+    };
+
+    let course = match client.get_courses().await {
+        Ok(courses) => courses.into_iter().next(),
+        Err(e) => {
+            report.fail(format!("Cannot read the course list: {e}"));
+            None
+        }
+    };
+    match &course {
+        Some(course) => report.info(format!("Course pages of {}", course.title)),
+        None => report.warn("No course found, course pages are skipped"),
+    }
+
+    for check in client.check_selectors(course.as_ref()).await {
+        let on_course_page = !matches!(check.scraper, "timetable" | "courses");
+        let message = format!("{}: `{}` on {}", check.scraper, check.selector, check.path);
+
+        match check.matches {
+            Ok(0) if on_course_page => {
+                report.warn(format!("{message} matched nothing, or the course has none"));
+            }
+            Ok(0) => report.fail(format!("{message} matched nothing")),
+            Ok(count) => report.ok(format!("{message} matched {count}")),
+            Err(e) => report.fail(format!("{message} could not be loaded: {e}")),
+        }
+    }
+}
+
+fn terminal(report: &mut Report) {
+    report.section("Terminal");
+
+    if std::env::var_os("NO_COLOR").is_some() {
+        report.info("Colors are disabled by NO_COLOR");
+    } else if !std::io::stdout().is_terminal() {
+        report.info("Colors are disabled, stdout is not a terminal");
+    } else {
+        report.ok("stdout is a terminal, colors are enabled");
+    }
+
+    // Colors in `[color]` are 24-bit, which terminals announce through COLORTERM
+    match std::env::var("COLORTERM") {
+        Ok(colorterm) if matches!(colorterm.as_str(), "truecolor" | "24bit") => {
+            report.ok(format!(
+                "24-bit colors are supported (COLORTERM={colorterm})"
+            ));
+        }
+        _ => report.warn(format!(
+            "COLORTERM does not announce 24-bit colors, so colors may look off (TERM={})",
+            std::env::var("TERM").unwrap_or_default()
+        )),
+    }
+
+    if is_interactive() {
+        report.ok("Prompts are enabled");
+    } else {
+        report.info("Prompts are disabled by --non-interactive, or stdin is not a terminal");
+    }
+}
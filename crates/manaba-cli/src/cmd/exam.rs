@@ -1,94 +1,81 @@
 use crate::color::AppColorize as _;
 use crate::{
-    cmd::{INDENT, colorize, colorize_bg, date_as_str},
+    cmd::{
+        INDENT, colorize, colorize_bg, date_as_str, filter::AssignmentFilter, local_label,
+        note_line, state_label,
+    },
+    courses,
     error::Result,
+    output::AssignmentRecord,
+    overlay, urgency,
 };
 use manaba_sdk::{
-    Client, Exam,
-    assignment::{
-        AssignmentDate, AssignmentImportanceLevel, AssignmentReceptibleState, AssignmentSubmitState,
-    },
+    Client,
+    assignment::AssignmentKind,
+    query::{Queryable as _, SortKey},
 };
 
-pub async fn exam(client: &Client, should_show_all: bool, should_show_warn: bool) -> Result<()> {
-    let courses = client.get_courses().await?;
-
-    for course in courses {
-        let exams = client.get_exams(&course).await?;
-
-        let exams = exams
-            .into_iter()
-            .filter(|report| {
-                if should_show_all {
-                    return true;
-                }
-
-                if should_show_warn {
-                    return matches!(
-                        report,
-                        Exam {
-                            submit_state: AssignmentSubmitState::Todo,
-                            receptiable_state: AssignmentReceptibleState::Open,
-                            due_date: Some(AssignmentDate {
-                                importance_level: AssignmentImportanceLevel::High
-                                    | AssignmentImportanceLevel::Medium,
-                                ..
-                            }),
-                            ..
-                        }
-                    );
-                }
-
-                matches!(
-                    report,
-                    Exam {
-                        submit_state: AssignmentSubmitState::Todo,
-                        receptiable_state: AssignmentReceptibleState::Open,
-                        ..
-                    }
-                )
-            })
+/// Print exams by course, and return how many of them are open and urgent
+pub async fn exam(client: &Client, filter: &AssignmentFilter) -> Result<usize> {
+    if !filter.query.includes_kind(AssignmentKind::Exam) {
+        return Ok(0);
+    }
+
+    let mut courses = courses::courses(client).await?;
+    courses.retain(|course| filter.query.matches_course(course));
+    if filter.query.sort == Some(SortKey::Course) {
+        courses.sort_by(|a, b| a.title.cmp(&b.title));
+    }
+    let mut urgent = 0;
+
+    for course in &courses {
+        let exams = client.get_exams(course).await?;
+
+        let mut exams = exams
+            .iter()
+            .map(|exam| (course, exam))
+            .filter(|item| filter.is_visible(item))
             .collect::<Vec<_>>();
+        filter.query.sort(&mut exams);
 
         if exams.is_empty() {
             continue;
         }
 
-        println!("{}", course.title.black().on_blue().with_bold());
+        println!("{}", courses::header(course));
+
+        for item in exams {
+            let (_, exam) = item;
+            let local = overlay::local_state(&exam.id);
+
+            if urgency::is_pending_urgent(
+                overlay::overlay().wrap(item).state(),
+                exam.due_date.as_ref().map(|due_date| &due_date.date),
+            ) {
+                urgent += 1;
+            }
 
-        for exam in exams {
             let header_str = {
-                let status_str = if should_show_all {
-                    match exam {
-                        Exam {
-                            receptiable_state: AssignmentReceptibleState::NotStarted,
-                            ..
-                        } => " WAITING ".black().on_gray(),
-
-                        Exam {
-                            submit_state: AssignmentSubmitState::Todo,
-                            receptiable_state: AssignmentReceptibleState::Open,
-                            ..
-                        } => " TODO ".black().on_red(),
-
-                        Exam {
-                            submit_state: AssignmentSubmitState::Done,
-                            receptiable_state:
-                                AssignmentReceptibleState::Closed | AssignmentReceptibleState::Open,
-                            ..
-                        } => " DONE ".aqua().on_gray(),
-
-                        _ => " CLOSED ".black().on_gray(),
-                    }
+                let status_str = if filter.shows_states() {
+                    state_label(overlay::overlay().wrap(item).state())
                 } else if let Some(due_date) = &exam.due_date {
-                    colorize_bg(" ", &exam.receptiable_state, &due_date.importance_level)
+                    colorize_bg(
+                        " ",
+                        &exam.receptiable_state,
+                        &urgency::importance_level(&due_date.date),
+                    )
                 } else {
                     String::new()
                 };
 
                 let title_str = format!(" {} ", exam.title);
 
-                format!("{INDENT}{}{}", status_str, title_str.on_black())
+                format!(
+                    "{INDENT}{}{}{}",
+                    status_str,
+                    title_str.on_black(),
+                    local_label(local)
+                )
             };
 
             let start_date_str = exam.start_date.as_ref().map_or(String::new(), date_as_str);
@@ -96,14 +83,19 @@ pub async fn exam(client: &Client, should_show_all: bool, should_show_warn: bool
             let due_date_str = exam.due_date.as_ref().map_or(String::new(), date_as_str);
 
             let content = format!(
-                "{INDENT}{INDENT}開始: {start_date_str}\n{INDENT}{INDENT}締切: {due_date_str}"
+                "{INDENT}{INDENT}開始: {start_date_str}\n{INDENT}{INDENT}締切: {due_date_str}{}",
+                note_line(local)
             );
 
             if let Some(due_date) = &exam.due_date {
                 println!(
                     "{}\n{}",
                     header_str,
-                    colorize(content, &exam.receptiable_state, &due_date.importance_level)
+                    colorize(
+                        content,
+                        &exam.receptiable_state,
+                        &urgency::importance_level(&due_date.date)
+                    )
                 );
             } else {
                 println!("{}\n{}", header_str, content);
@@ -113,5 +105,34 @@ pub async fn exam(client: &Client, should_show_all: bool, should_show_warn: bool
         println!();
     }
 
-    Ok(())
+    Ok(urgent)
+}
+
+pub async fn exam_records(
+    client: &Client,
+    filter: &AssignmentFilter,
+) -> Result<Vec<AssignmentRecord>> {
+    if !filter.query.includes_kind(AssignmentKind::Exam) {
+        return Ok(Vec::new());
+    }
+
+    let courses = courses::courses(client).await?;
+    let mut records = Vec::new();
+
+    for course in courses
+        .iter()
+        .filter(|course| filter.query.matches_course(course))
+    {
+        let exams = client.get_exams(course).await?;
+
+        records.extend(
+            exams
+                .iter()
+                .map(|exam| (course, exam))
+                .filter(|item| filter.is_visible(item))
+                .map(|(course, exam)| AssignmentRecord::from_exam(course, exam)),
+        );
+    }
+
+    Ok(records)
 }
@@ -0,0 +1,205 @@
+use crate::{
+    cmd::complete,
+    error::Result,
+    overlay,
+    timezone::{self, TimeArg},
+    urgency,
+};
+use chrono::{DateTime, Duration, Utc};
+use clap::{Args, ValueEnum};
+use clap_complete::ArgValueCandidates;
+use manaba_sdk::{
+    assignment::{AssignmentImportanceLevel, AssignmentKind},
+    query::{AssignmentQuery, AssignmentState, Queryable, SortKey},
+};
+
+#[derive(Args)]
+pub struct FilterArgs {
+    /// show assignments in every state
+    #[arg(short, long)]
+    all: bool,
+    /// filter by approaching deadlines
+    #[arg(short, long)]
+    warn: bool,
+    /// filter by course title or id
+    #[arg(long, value_name = "PATTERN", add = ArgValueCandidates::new(complete::course_candidates))]
+    course: Option<String>,
+    /// only deadlines before this time: 2026-10-26, 2026-10-26T09:00 or from now such as 7d
+    #[arg(long, value_name = "TIME", value_parser = parse_due_bound)]
+    due_before: Option<DueBound>,
+    /// only deadlines at or after this time, in the same formats as --due-before
+    #[arg(long, value_name = "TIME", value_parser = parse_due_bound)]
+    due_after: Option<DueBound>,
+    /// filter by state, can be repeated (default: todo)
+    #[arg(long, value_enum)]
+    state: Vec<StateArg>,
+    /// filter by kind, can be repeated
+    #[arg(long, value_enum)]
+    kind: Vec<KindArg>,
+    /// filter by assignment title
+    #[arg(long, value_name = "TEXT")]
+    search: Option<String>,
+    /// sort order, keeping the course order when omitted
+    #[arg(long, value_enum)]
+    sort: Option<SortArg>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StateArg {
+    Todo,
+    Done,
+    Closed,
+    Waiting,
+    /// marked with `manaba mark <id> ignored`
+    Ignored,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum KindArg {
+    Report,
+    Exam,
+    /// personal items from `manaba todo`
+    Todo,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortArg {
+    Due,
+    Course,
+    Title,
+}
+
+/// A deadline bound, resolved after `--as-of` is applied
+#[derive(Clone, Copy)]
+enum DueBound {
+    At(TimeArg),
+    FromNow(Duration),
+}
+
+/// `FilterArgs` resolved into a query
+pub struct AssignmentFilter {
+    pub query: AssignmentQuery,
+    warn: bool,
+}
+
+impl AssignmentFilter {
+    pub fn new(args: &FilterArgs) -> Result<Self> {
+        let states = if !args.state.is_empty() {
+            args.state.iter().map(|state| (*state).into()).collect()
+        } else if args.all {
+            Vec::new()
+        } else {
+            vec![AssignmentState::Todo]
+        };
+
+        let query = AssignmentQuery {
+            course: args.course.clone(),
+            due_before: args.due_before.map(DueBound::resolve).transpose()?,
+            due_after: args.due_after.map(DueBound::resolve).transpose()?,
+            states,
+            kinds: args.kind.iter().map(|kind| (*kind).into()).collect(),
+            search: args.search.clone(),
+            sort: args.sort.map(Into::into),
+        };
+
+        Ok(AssignmentFilter {
+            query,
+            warn: args.warn && !args.all,
+        })
+    }
+
+    /// Every assignment, in every state
+    pub fn all() -> Self {
+        AssignmentFilter {
+            query: AssignmentQuery::default(),
+            warn: false,
+        }
+    }
+
+    /// Whether `item` matches, with its state from the local overlay
+    pub fn is_visible(&self, item: &impl Queryable) -> bool {
+        let item = overlay::overlay().wrap(item);
+
+        if !self.query.matches(&item) {
+            return false;
+        }
+
+        if self.warn {
+            return item.state() == AssignmentState::Todo
+                && item.due_date().is_some_and(|due_date| {
+                    let importance_level = urgency::importance_level(&due_date);
+                    urgency::is_urgent(importance_level)
+                        || importance_level == AssignmentImportanceLevel::Medium
+                });
+        }
+
+        true
+    }
+
+    /// Whether states other than todo can be listed, so each one needs a label
+    pub fn shows_states(&self) -> bool {
+        self.query.states != [AssignmentState::Todo]
+    }
+}
+
+impl DueBound {
+    fn resolve(self) -> Result<DateTime<Utc>> {
+        match self {
+            DueBound::At(time) => time.resolve(),
+            DueBound::FromNow(duration) => Ok(timezone::now().to_utc() + duration),
+        }
+    }
+}
+
+/// Parse a time accepted by `--as-of`, a date, or a duration from now such as `12h`, `7d` or `2w`
+fn parse_due_bound(s: &str) -> Result<DueBound, String> {
+    if let Some(unit) = s.chars().last()
+        && let Ok(amount) = s[..s.len() - unit.len_utf8()].parse::<i64>()
+    {
+        let duration = match unit {
+            'h' => Duration::hours(amount),
+            'd' => Duration::days(amount),
+            'w' => Duration::weeks(amount),
+            _ => {
+                return Err(format!(
+                    "invalid duration `{s}`, expected e.g. 12h, 7d or 2w"
+                ));
+            }
+        };
+        return Ok(DueBound::FromNow(duration));
+    }
+
+    timezone::parse_date_time(s).map(DueBound::At)
+}
+
+impl From<StateArg> for AssignmentState {
+    fn from(state: StateArg) -> Self {
+        match state {
+            StateArg::Todo => AssignmentState::Todo,
+            StateArg::Done => AssignmentState::Done,
+            StateArg::Closed => AssignmentState::Closed,
+            StateArg::Waiting => AssignmentState::Waiting,
+            StateArg::Ignored => AssignmentState::Ignored,
+        }
+    }
+}
+
+impl From<KindArg> for AssignmentKind {
+    fn from(kind: KindArg) -> Self {
+        match kind {
+            KindArg::Report => AssignmentKind::Report,
+            KindArg::Exam => AssignmentKind::Exam,
+            KindArg::Todo => AssignmentKind::Todo,
+        }
+    }
+}
+
+impl From<SortArg> for SortKey {
+    fn from(sort: SortArg) -> Self {
+        match sort {
+            SortArg::Due => SortKey::Due,
+            SortArg::Course => SortKey::Course,
+            SortArg::Title => SortKey::Title,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{error::Result, overlay};
+use clap::ValueEnum;
+use manaba_sdk::overlay::LocalStatus;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub(crate) enum MarkStatus {
+    Started,
+    /// optional assignments that will never be submitted
+    Ignored,
+    /// submitted outside manaba, such as on paper
+    DoneOffline,
+    /// remove the local status
+    Clear,
+}
+
+/// Set the local status or note of an assignment, or print them when neither is given
+pub fn mark(id: &str, status: Option<MarkStatus>, note: Option<String>) -> Result<()> {
+    // Ids are the last segment of assignment URLs, so pasted URLs work too
+    let id = id.trim_end_matches('/').rsplit('/').next().unwrap_or(id);
+
+    let mut overlay = overlay::load()?;
+    let mut local = overlay.get(id).cloned().unwrap_or_default();
+
+    if status.is_some() || note.is_some() {
+        if let Some(status) = status {
+            local.status = match status {
+                MarkStatus::Started => Some(LocalStatus::Started),
+                MarkStatus::Ignored => Some(LocalStatus::Ignored),
+                MarkStatus::DoneOffline => Some(LocalStatus::DoneOffline),
+                MarkStatus::Clear => None,
+            };
+        }
+        if let Some(note) = note {
+            local.note = Some(note).filter(|note| !note.is_empty());
+        }
+
+        overlay.set(id, local.clone());
+        overlay.save(&overlay::overlay_path())?;
+    }
+
+    let status = match local.status {
+        Some(LocalStatus::Started) => "started",
+        Some(LocalStatus::Ignored) => "ignored",
+        Some(LocalStatus::DoneOffline) => "done offline",
+        None => "no local status",
+    };
+    match &local.note {
+        Some(note) => println!("{id}: {status} ({note})"),
+        None => println!("{id}: {status}"),
+    }
+
+    Ok(())
+}
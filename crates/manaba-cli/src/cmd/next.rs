@@ -0,0 +1,88 @@
+use crate::{
+    cmd::timetable::{period_range, period_time, weekday_label},
+    courses,
+    error::{Error, Result},
+    output::{OutputFormat, TimetableRecord, print_records},
+    timezone,
+};
+use chrono::{Datelike as _, Duration, TimeZone as _};
+use manaba_sdk::{Client, TimetableEntry};
+use std::collections::HashMap;
+
+pub async fn next(
+    client: &Client,
+    timetable: &HashMap<String, String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let now = timezone::institution_now();
+    let entries = client.get_timetable().await?;
+
+    if !entries.is_empty()
+        && entries
+            .iter()
+            .all(|entry| period_range(timetable, entry.period).is_none())
+    {
+        return Err(Error::MissingConfig("timetable"));
+    }
+
+    // Search a week ahead, so a class later on the same weekday is found too
+    let next = (0..=7)
+        .flat_map(|days| {
+            let date = now.date_naive() + Duration::days(days);
+
+            let mut classes = entries
+                .iter()
+                .filter(|entry| entry.weekday == date.weekday())
+                .filter_map(|entry| {
+                    let (start, _) = period_range(timetable, entry.period)?;
+                    let start = now
+                        .timezone()
+                        .from_local_datetime(&date.and_time(start))
+                        .single()?;
+                    Some((start, entry))
+                })
+                .collect::<Vec<_>>();
+            classes.sort_by_key(|(start, _)| *start);
+            classes
+        })
+        .find(|(start, _)| *start > now);
+
+    let Some((start, entry)) = next else {
+        println!("No upcoming classes");
+        return Ok(());
+    };
+
+    if format != OutputFormat::Text {
+        let record = TimetableRecord::from_entry(entry, period_time(timetable, entry.period));
+        return print_records(&[record], format);
+    }
+
+    println!(
+        "{} {}限 {}  {}{} (in {})",
+        weekday_label(entry.weekday),
+        entry.period,
+        timezone::to_display(&start).format("%H:%M"),
+        courses::short_title(&entry.course),
+        room(entry),
+        format_duration(start - now)
+    );
+
+    Ok(())
+}
+
+fn room(entry: &TimetableEntry) -> String {
+    entry
+        .room
+        .as_ref()
+        .map_or(String::new(), |room| format!(" @ {room}"))
+}
+
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+
+    match (minutes / (24 * 60), minutes / 60 % 24, minutes % 60) {
+        (0, 0, m) => format!("{m}m"),
+        (0, h, m) => format!("{h}h {m}m"),
+        (d, h, _) => format!("{d}d {h}h"),
+    }
+}
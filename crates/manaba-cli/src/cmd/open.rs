@@ -0,0 +1,176 @@
+use crate::{
+    cache,
+    cmd::{date_as_str, state_label},
+    courses,
+    error::{Error, Result},
+    overlay,
+    picker::{self, PickItem},
+};
+use fuzzy_matcher::{FuzzyMatcher as _, skim::SkimMatcherV2};
+use manaba_sdk::{
+    Client,
+    assignment::AssignmentDate,
+    query::{AssignmentState, Queryable},
+};
+
+/// A page that `manaba open` can jump to
+struct Target {
+    /// course title followed by the item title, which the query is matched against
+    text: String,
+    label: String,
+    url: String,
+    preview: String,
+}
+
+/// Open the course, report, exam or news page whose title best matches `query`,
+/// or the one picked with the fuzzy finder when there is no query
+pub async fn open(client: &Client, query: Option<&str>, print: bool) -> Result<()> {
+    let targets = targets(client).await?;
+
+    let target = match query {
+        Some(query) => {
+            let matcher = SkimMatcherV2::default().ignore_case();
+
+            targets
+                .iter()
+                .filter_map(|target| Some((matcher.fuzzy_match(&target.text, query)?, target)))
+                // `max_by_key` keeps the last maximum, so reverse to prefer earlier courses on ties
+                .rev()
+                .max_by_key(|(score, _)| *score)
+                .map(|(_, target)| target)
+                .ok_or_else(|| Error::NoMatch(query.to_owned()))?
+        }
+        None => {
+            let items = targets
+                .iter()
+                .map(|target| PickItem {
+                    label: target.label.clone(),
+                    preview: target.preview.clone(),
+                })
+                .collect::<Vec<_>>();
+
+            match picker::pick("Select a page to open", &items)? {
+                Some(index) => &targets[index],
+                None => return Ok(()),
+            }
+        }
+    };
+
+    if print {
+        println!("{}", target.url);
+    } else {
+        println!("Opening {}", target.label);
+        opener::open(&target.url)?;
+    }
+
+    Ok(())
+}
+
+async fn targets(client: &Client) -> Result<Vec<Target>> {
+    let courses = courses::courses(client).await?;
+    let mut targets = Vec::new();
+
+    for course in &courses {
+        let reports = client.get_reports(course).await?;
+        let exams = client.get_exams(course).await?;
+
+        // Detail pages of the course and its open assignments, which are the ones worth reading
+        let open_ids = reports
+            .iter()
+            .filter(|report| {
+                overlay::overlay().wrap((course, *report)).state() == AssignmentState::Todo
+            })
+            .map(|report| report.id.as_str())
+            .chain(
+                exams
+                    .iter()
+                    .filter(|exam| {
+                        overlay::overlay().wrap((course, *exam)).state() == AssignmentState::Todo
+                    })
+                    .map(|exam| exam.id.as_str()),
+            );
+        let ids = std::iter::once(course.id.as_str())
+            .chain(open_ids)
+            .collect::<Vec<_>>();
+        let pages = cache::page_texts(client, &ids).await;
+        let page = |id: &str| pages.get(id).map(Vec::as_slice);
+
+        let mut preview = vec![course.title.clone()];
+        picker::push_page(&mut preview, page(&course.id));
+        targets.push(Target {
+            text: course.title.clone(),
+            label: course.title.clone(),
+            url: course.url(&client.base_url),
+            preview: preview.join("\n"),
+        });
+
+        for report in &reports {
+            targets.push(Target {
+                text: format!("{} {}", course.title, report.title),
+                label: format!("[Report] {} ({})", report.title, course.title),
+                url: report.url(&client.base_url),
+                preview: assignment_preview(
+                    (course, report),
+                    report.start_date.as_ref(),
+                    report.due_date.as_ref(),
+                    page(&report.id),
+                ),
+            });
+        }
+
+        for exam in &exams {
+            targets.push(Target {
+                text: format!("{} {}", course.title, exam.title),
+                label: format!("[Exam] {} ({})", exam.title, course.title),
+                url: exam.url(&client.base_url),
+                preview: assignment_preview(
+                    (course, exam),
+                    exam.start_date.as_ref(),
+                    exam.due_date.as_ref(),
+                    page(&exam.id),
+                ),
+            });
+        }
+
+        for news in client.get_news(course).await? {
+            let posted_at = news.posted_at.map_or(String::new(), |posted_at| {
+                posted_at.format("%Y-%m-%d %H:%M").to_string()
+            });
+
+            targets.push(Target {
+                text: format!("{} {}", course.title, news.title),
+                label: format!("[News] {} ({})", news.title, course.title),
+                url: news.url(&client.base_url),
+                preview: format!("{}\n{}\n投稿: {posted_at}", news.title, course.title),
+            });
+        }
+    }
+
+    Ok(targets)
+}
+
+fn assignment_preview(
+    item: impl Queryable,
+    start_date: Option<&AssignmentDate>,
+    due_date: Option<&AssignmentDate>,
+    page: Option<&[String]>,
+) -> String {
+    let item = overlay::overlay().wrap(item);
+    let local = item.local;
+
+    let mut lines = vec![
+        item.title().to_owned(),
+        item.course_title().to_owned(),
+        format!("開始: {}", start_date.map_or(String::new(), date_as_str)),
+        format!("締切: {}", due_date.map_or(String::new(), date_as_str)),
+    ];
+    if item.state() != AssignmentState::Todo {
+        lines.push(state_label(item.state()));
+    }
+    if let Some(note) = local.and_then(|local| local.note.as_ref()) {
+        lines.push(format!("メモ: {note}"));
+    }
+    picker::push_page(&mut lines, page);
+
+    lines.join("\n")
+}
@@ -1,94 +1,81 @@
 use crate::color::AppColorize as _;
 use crate::{
-    cmd::{INDENT, colorize, colorize_bg, date_as_str},
+    cmd::{
+        INDENT, colorize, colorize_bg, date_as_str, filter::AssignmentFilter, local_label,
+        note_line, state_label,
+    },
+    courses,
     error::Result,
+    output::AssignmentRecord,
+    overlay, urgency,
 };
 use manaba_sdk::{
-    Client, Report,
-    assignment::{
-        AssignmentDate, AssignmentImportanceLevel, AssignmentReceptibleState, AssignmentSubmitState,
-    },
+    Client,
+    assignment::AssignmentKind,
+    query::{Queryable as _, SortKey},
 };
 
-pub async fn report(client: &Client, should_show_all: bool, should_show_warn: bool) -> Result<()> {
-    let courses = client.get_courses().await?;
-
-    for course in courses {
-        let reports = client.get_reports(&course).await?;
-
-        let reports = reports
-            .into_iter()
-            .filter(|report| {
-                if should_show_all {
-                    return true;
-                }
-
-                if should_show_warn {
-                    return matches!(
-                        report,
-                        Report {
-                            submit_state: AssignmentSubmitState::Todo,
-                            receptiable_state: AssignmentReceptibleState::Open,
-                            due_date: Some(AssignmentDate {
-                                importance_level: AssignmentImportanceLevel::High
-                                    | AssignmentImportanceLevel::Medium,
-                                ..
-                            }),
-                            ..
-                        }
-                    );
-                }
-
-                matches!(
-                    report,
-                    Report {
-                        submit_state: AssignmentSubmitState::Todo,
-                        receptiable_state: AssignmentReceptibleState::Open,
-                        ..
-                    }
-                )
-            })
+/// Print reports by course, and return how many of them are open and urgent
+pub async fn report(client: &Client, filter: &AssignmentFilter) -> Result<usize> {
+    if !filter.query.includes_kind(AssignmentKind::Report) {
+        return Ok(0);
+    }
+
+    let mut courses = courses::courses(client).await?;
+    courses.retain(|course| filter.query.matches_course(course));
+    if filter.query.sort == Some(SortKey::Course) {
+        courses.sort_by(|a, b| a.title.cmp(&b.title));
+    }
+    let mut urgent = 0;
+
+    for course in &courses {
+        let reports = client.get_reports(course).await?;
+
+        let mut reports = reports
+            .iter()
+            .map(|report| (course, report))
+            .filter(|item| filter.is_visible(item))
             .collect::<Vec<_>>();
+        filter.query.sort(&mut reports);
 
         if reports.is_empty() {
             continue;
         }
 
-        println!("{}", course.title.black().on_blue().with_bold());
+        println!("{}", courses::header(course));
+
+        for item in reports {
+            let (_, report) = item;
+            let local = overlay::local_state(&report.id);
+
+            if urgency::is_pending_urgent(
+                overlay::overlay().wrap(item).state(),
+                report.due_date.as_ref().map(|due_date| &due_date.date),
+            ) {
+                urgent += 1;
+            }
 
-        for report in reports {
             let header_str = {
-                let status_str = if should_show_all {
-                    match report {
-                        Report {
-                            receptiable_state: AssignmentReceptibleState::NotStarted,
-                            ..
-                        } => " WAITING ".black().on_gray(),
-
-                        Report {
-                            submit_state: AssignmentSubmitState::Todo,
-                            receptiable_state: AssignmentReceptibleState::Open,
-                            ..
-                        } => " TODO ".black().on_red(),
-
-                        Report {
-                            submit_state: AssignmentSubmitState::Done,
-                            receptiable_state:
-                                AssignmentReceptibleState::Closed | AssignmentReceptibleState::Open,
-                            ..
-                        } => " DONE ".aqua().on_gray(),
-
-                        _ => " CLOSED ".black().on_gray(),
-                    }
+                let status_str = if filter.shows_states() {
+                    state_label(overlay::overlay().wrap(item).state())
                 } else if let Some(due_date) = &report.due_date {
-                    colorize_bg(" ", &report.receptiable_state, &due_date.importance_level)
+                    colorize_bg(
+                        " ",
+                        &report.receptiable_state,
+                        &urgency::importance_level(&due_date.date),
+                    )
                 } else {
                     String::new()
                 };
 
                 let title_str = format!(" {} ", report.title);
 
-                format!("{INDENT}{}{}", status_str, title_str.on_black())
+                format!(
+                    "{INDENT}{}{}{}",
+                    status_str,
+                    title_str.on_black(),
+                    local_label(local)
+                )
             };
 
             let start_date_str = report
@@ -99,8 +86,10 @@ pub async fn report(client: &Client, should_show_all: bool, should_show_warn: bo
             let due_date_str = report.due_date.as_ref().map_or(String::new(), date_as_str);
 
             let content = format!(
-                "{INDENT}{INDENT}開始: {}\n{INDENT}{INDENT}締切: {}",
-                start_date_str, due_date_str
+                "{INDENT}{INDENT}開始: {}\n{INDENT}{INDENT}締切: {}{}",
+                start_date_str,
+                due_date_str,
+                note_line(local)
             );
 
             if let Some(due_date) = &report.due_date {
@@ -110,7 +99,7 @@ pub async fn report(client: &Client, should_show_all: bool, should_show_warn: bo
                     colorize(
                         content,
                         &report.receptiable_state,
-                        &due_date.importance_level
+                        &urgency::importance_level(&due_date.date)
                     )
                 );
             } else {
@@ -121,5 +110,34 @@ pub async fn report(client: &Client, should_show_all: bool, should_show_warn: bo
         println!();
     }
 
-    Ok(())
+    Ok(urgent)
+}
+
+pub async fn report_records(
+    client: &Client,
+    filter: &AssignmentFilter,
+) -> Result<Vec<AssignmentRecord>> {
+    if !filter.query.includes_kind(AssignmentKind::Report) {
+        return Ok(Vec::new());
+    }
+
+    let courses = courses::courses(client).await?;
+    let mut records = Vec::new();
+
+    for course in courses
+        .iter()
+        .filter(|course| filter.query.matches_course(course))
+    {
+        let reports = client.get_reports(course).await?;
+
+        records.extend(
+            reports
+                .iter()
+                .map(|report| (course, report))
+                .filter(|item| filter.is_visible(item))
+                .map(|(course, report)| AssignmentRecord::from_report(course, report)),
+        );
+    }
+
+    Ok(records)
 }
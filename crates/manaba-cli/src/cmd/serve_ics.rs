@@ -0,0 +1,255 @@
+use crate::{
+    cmd::{AssignmentFilter, todo_records},
+    courses,
+    error::{Result, print_err},
+    ics::{IcsEvent, render_calendar},
+};
+use chrono::Utc;
+use manaba_sdk::Client;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    net::{TcpListener, TcpStream},
+    sync::RwLock,
+};
+
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+
+type Feed = Arc<RwLock<Option<String>>>;
+
+pub async fn serve_ics(client: Client, bind: SocketAddr, interval_minutes: u64) -> Result<()> {
+    let feed: Feed = Arc::new(RwLock::new(None));
+
+    let listener = TcpListener::bind(bind).await?;
+    println!("Serving calendar feed on http://{bind}/manaba.ics");
+
+    tokio::spawn(refresh_loop(
+        client,
+        feed.clone(),
+        Duration::from_secs(interval_minutes.max(1) * 60),
+    ));
+
+    serve(listener, feed).await
+}
+
+async fn serve(listener: TcpListener, feed: Feed) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let feed = feed.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = respond(stream, feed).await {
+                print_err(format!("Failed to respond: {e}"));
+            }
+        });
+    }
+}
+
+async fn refresh_loop(client: Client, feed: Feed, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+
+    loop {
+        interval.tick().await;
+        update_feed(&feed, fetch_events(&client).await).await;
+    }
+}
+
+/// Replace the feed with fetched events, or keep the last good one when fetching failed
+async fn update_feed(feed: &Feed, events: Result<Vec<IcsEvent>>) {
+    match events {
+        Ok(events) => {
+            let calendar = render_calendar(&events);
+            *feed.write().await = Some(calendar);
+            println!("Feed refreshed: {} deadlines", events.len());
+        }
+        Err(e) => {
+            print_err(format!(
+                "Failed to refresh feed, serving last good data: {e}"
+            ));
+        }
+    }
+}
+
+async fn fetch_events(client: &Client) -> Result<Vec<IcsEvent>> {
+    let courses = courses::courses(client).await?;
+    let mut events = Vec::new();
+
+    for course in courses {
+        for report in client.get_reports(&course).await? {
+            if let Some(due_date) = &report.due_date {
+                events.push(IcsEvent {
+                    uid: format!("{}@manaba", report.id),
+                    summary: format!("[Report] {}", report.title),
+                    description: course.title.clone(),
+                    url: report.url(&client.base_url),
+                    due: due_date.date.with_timezone(&Utc),
+                });
+            }
+        }
+
+        for exam in client.get_exams(&course).await? {
+            if let Some(due_date) = &exam.due_date {
+                events.push(IcsEvent {
+                    uid: format!("{}@manaba", exam.id),
+                    summary: format!("[Exam] {}", exam.title),
+                    description: course.title.clone(),
+                    url: exam.url(&client.base_url),
+                    due: due_date.date.with_timezone(&Utc),
+                });
+            }
+        }
+    }
+
+    for todo in todo_records(&AssignmentFilter::all())? {
+        if let Some(due_date) = todo.due_date {
+            events.push(IcsEvent {
+                uid: format!("{}@manaba", todo.id),
+                summary: format!("[Todo] {}", todo.title),
+                description: todo.course_title.clone(),
+                url: todo.url(&client.base_url),
+                due: due_date.to_utc(),
+            });
+        }
+    }
+
+    Ok(events)
+}
+
+async fn respond(mut stream: TcpStream, feed: Feed) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0; 1024];
+
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < MAX_REQUEST_SIZE {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let request = String::from_utf8_lossy(&buf);
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+
+    let response = match (method, path) {
+        ("GET" | "HEAD", "/" | "/manaba.ics") => match feed.read().await.as_ref() {
+            Some(calendar) => http_response("200 OK", "text/calendar; charset=utf-8", calendar),
+            None => http_response(
+                "503 Service Unavailable",
+                "text/plain; charset=utf-8",
+                "Feed is not ready yet\n",
+            ),
+        },
+        ("GET" | "HEAD", _) => {
+            http_response("404 Not Found", "text/plain; charset=utf-8", "Not Found\n")
+        }
+        _ => http_response(
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            "Method Not Allowed\n",
+        ),
+    };
+
+    let response = if method == "HEAD" {
+        response.split_once("\r\n\r\n").unwrap().0.to_owned() + "\r\n\r\n"
+    } else {
+        response
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
+
+fn http_response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use chrono::TimeZone as _;
+
+    fn event(summary: &str) -> IcsEvent {
+        IcsEvent {
+            uid: "course_11111_report_22222@manaba".to_owned(),
+            summary: summary.to_owned(),
+            description: "11111:Operating Systems".to_owned(),
+            url: "https://manaba.example.com/ct/course_11111_report_22222".to_owned(),
+            due: Utc.with_ymd_and_hms(2026, 10, 20, 14, 59, 0).unwrap(),
+        }
+    }
+
+    async fn server(feed: Feed) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve(listener, feed));
+        url
+    }
+
+    async fn get(url: &str) -> (u16, String, String) {
+        let response = reqwest::get(url).await.unwrap();
+        let status = response.status().as_u16();
+        let content_type = response.headers()[reqwest::header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_owned();
+        (status, content_type, response.text().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn respond_test() {
+        let feed: Feed = Arc::new(RwLock::new(None));
+        let url = server(feed.clone()).await;
+
+        let (status, _, _) = get(&format!("{url}/manaba.ics")).await;
+        assert_eq!(status, 503);
+
+        update_feed(&feed, Ok(vec![event("[Report] Essay")])).await;
+        let (status, content_type, body) = get(&format!("{url}/manaba.ics")).await;
+        assert_eq!(status, 200);
+        assert_eq!(content_type, "text/calendar; charset=utf-8");
+        assert!(body.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(body.contains("SUMMARY:[Report] Essay\r\n"));
+
+        let (status, _, body) = get(&format!("{url}/?token=1")).await;
+        assert_eq!(status, 200);
+        assert!(body.contains("SUMMARY:[Report] Essay\r\n"));
+
+        let (status, content_type, _) = get(&format!("{url}/calendar.ics")).await;
+        assert_eq!(status, 404);
+        assert_eq!(content_type, "text/plain; charset=utf-8");
+    }
+
+    #[tokio::test]
+    async fn failed_refresh_test() {
+        let feed: Feed = Arc::new(RwLock::new(None));
+        let url = server(feed.clone()).await;
+
+        update_feed(&feed, Ok(vec![event("[Report] Essay")])).await;
+        update_feed(&feed, Err(Error::Io(std::io::ErrorKind::TimedOut.into()))).await;
+
+        let (status, _, body) = get(&format!("{url}/manaba.ics")).await;
+        assert_eq!(status, 200);
+        assert!(body.contains("SUMMARY:[Report] Essay\r\n"));
+
+        update_feed(&feed, Ok(Vec::new())).await;
+        let (_, _, body) = get(&format!("{url}/manaba.ics")).await;
+        assert!(!body.contains("BEGIN:VEVENT"));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    APP_CONFIG, cache, client,
+    color::APP_COLOR,
+    error::{EXIT_BAR_URGENT, EXIT_MEDIUM, EXIT_URGENT, Result, print_err},
+    output::AssignmentRecord,
+    urgency,
+};
+use manaba_sdk::{
+    assignment::AssignmentImportanceLevel,
+    query::{AssignmentState, Queryable as _},
+};
+use serde::Serialize;
+use std::{process::ExitCode, time::Duration};
+
+/// Output for waybar `return-type = "json"` modules
+#[derive(Serialize)]
+struct StatusJson {
+    text: String,
+    tooltip: String,
+    class: &'static str,
+    high: usize,
+    medium: usize,
+    low: usize,
+}
+
+/// Exit with 3 or 7 by urgency for scripts, but only with 33 for urgent in status bars
+pub async fn status(
+    json: bool,
+    i3blocks: bool,
+    max_age_minutes: u64,
+    should_refresh: bool,
+) -> Result<ExitCode> {
+    let assignments = assignments(max_age_minutes, should_refresh).await?;
+
+    let open_assignments = assignments
+        .iter()
+        .filter(|assignment| assignment.state() == AssignmentState::Todo)
+        .collect::<Vec<_>>();
+
+    let count = |is_level: fn(AssignmentImportanceLevel) -> bool| {
+        open_assignments
+            .iter()
+            .filter(|assignment| assignment.importance.is_some_and(is_level))
+            .count()
+    };
+    let (high, medium, low) = (
+        count(urgency::is_urgent),
+        count(|level| level == AssignmentImportanceLevel::Medium),
+        count(|level| level == AssignmentImportanceLevel::Low),
+    );
+
+    let next = open_assignments
+        .iter()
+        .filter(|assignment| {
+            !matches!(
+                assignment.importance,
+                Some(AssignmentImportanceLevel::None | AssignmentImportanceLevel::Overdue)
+            )
+        })
+        .filter_map(|assignment| assignment.due_date.map(|due_date| (due_date, assignment)))
+        .min_by_key(|(due_date, _)| *due_date);
+
+    let counts = format!("⚠{high} ●{medium} ○{low}");
+    let mut text = counts.clone();
+    if let Some((due_date, assignment)) = next {
+        text.push_str(&format!(
+            " | {} {}",
+            due_date.format("%m/%d %H:%M"),
+            assignment.title
+        ));
+    }
+
+    let (class, exit_code) = if high > 0 {
+        ("high", EXIT_URGENT)
+    } else if medium > 0 {
+        ("medium", EXIT_MEDIUM)
+    } else if low > 0 {
+        ("low", 0)
+    } else {
+        ("none", 0)
+    };
+    let bar_exit_code = if high > 0 { EXIT_BAR_URGENT } else { 0 };
+
+    if i3blocks {
+        let app_color = APP_COLOR.get_or_init(Default::default);
+        let color = match class {
+            "high" => format!("#{}", app_color.red),
+            "medium" => format!("#{}", app_color.yellow),
+            _ => String::new(),
+        };
+
+        println!("{text}\n{counts}\n{color}");
+        return Ok(ExitCode::from(bar_exit_code));
+    }
+
+    if json {
+        let tooltip = open_assignments
+            .iter()
+            .map(|assignment| {
+                let due_date = assignment
+                    .due_date
+                    .map_or(String::new(), |d| d.format("%m/%d %H:%M").to_string());
+                if assignment.course_title.is_empty() {
+                    format!("{due_date} {}", assignment.title)
+                } else {
+                    format!(
+                        "{due_date} {} ({})",
+                        assignment.title, assignment.course_title
+                    )
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let status = StatusJson {
+            text,
+            tooltip,
+            class,
+            high,
+            medium,
+            low,
+        };
+        println!("{}", serde_json::to_string(&status)?);
+        return Ok(ExitCode::from(bar_exit_code));
+    }
+
+    println!("{text}");
+    Ok(ExitCode::from(exit_code))
+}
+
+async fn assignments(max_age_minutes: u64, should_refresh: bool) -> Result<Vec<AssignmentRecord>> {
+    if !should_refresh
+        && let Some(assignments) = cache::load(Duration::from_secs(max_age_minutes * 60))
+    {
+        return Ok(assignments);
+    }
+
+    let app_config = APP_CONFIG.get().unwrap();
+    let refreshed = async {
+        let client = client(app_config).await?;
+        cache::refresh(&client).await
+    }
+    .await;
+
+    match refreshed {
+        Ok(assignments) => Ok(assignments),
+        Err(e) => match cache::load_stale() {
+            Some(assignments) => {
+                print_err(format!("Failed to refresh, using cached assignments: {e}"));
+                Ok(assignments)
+            }
+            None => Err(e),
+        },
+    }
+}
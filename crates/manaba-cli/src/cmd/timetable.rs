@@ -1,6 +1,174 @@
-use std::collections::HashMap;
+use crate::{color::AppColorize as _, courses, error::Result, output::TimetableRecord};
+use chrono::{NaiveTime, Weekday};
+use manaba_sdk::{Client, TimetableEntry};
+use std::collections::{BTreeSet, HashMap};
+use unicode_width::{UnicodeWidthChar as _, UnicodeWidthStr as _};
 
-pub fn timetable(timetable: &HashMap<String, String>) {
+const CELL_WIDTH: usize = 14;
+const LABEL_WIDTH: usize = 13;
+
+pub async fn timetable(client: &Client, timetable: &HashMap<String, String>) -> Result<()> {
+    let entries = client.get_timetable().await?;
+
+    if entries.is_empty() {
+        print_period_times(timetable);
+    } else {
+        print_grid(&entries, timetable);
+    }
+
+    Ok(())
+}
+
+fn print_grid(entries: &[TimetableEntry], timetable: &HashMap<String, String>) {
+    let weekdays = [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ]
+    .into_iter()
+    .filter(|weekday| {
+        !matches!(weekday, Weekday::Sat | Weekday::Sun)
+            || entries.iter().any(|entry| entry.weekday == *weekday)
+    })
+    .collect::<Vec<_>>();
+
+    let periods = entries
+        .iter()
+        .map(|entry| entry.period)
+        .chain(timetable.keys().filter_map(|key| parse_period(key)))
+        .collect::<BTreeSet<_>>();
+
+    let separator = format!(
+        "{}{}",
+        "-".repeat(LABEL_WIDTH),
+        format!("+{}", "-".repeat(CELL_WIDTH)).repeat(weekdays.len())
+    );
+
+    let mut header = pad("", LABEL_WIDTH);
+    for weekday in &weekdays {
+        header.push('|');
+        header.push_str(&pad(weekday_label(*weekday), CELL_WIDTH));
+    }
+
+    println!("{}", header.black().on_blue());
+
+    for period in periods {
+        let time = period_time(timetable, period).map_or("", String::as_str);
+
+        let mut title_line = pad(&format!("{period}"), LABEL_WIDTH);
+        let mut room_line = pad(time, LABEL_WIDTH);
+
+        for weekday in &weekdays {
+            let slot = entries
+                .iter()
+                .filter(|entry| entry.weekday == *weekday && entry.period == period)
+                .collect::<Vec<_>>();
+
+            let titles = slot
+                .iter()
+                .map(|entry| courses::short_title(&entry.course))
+                .collect::<Vec<_>>()
+                .join("/");
+            let rooms = slot
+                .iter()
+                .filter_map(|entry| entry.room.as_deref())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            title_line.push('|');
+            title_line.push_str(&pad(&titles, CELL_WIDTH));
+            room_line.push('|');
+            room_line.push_str(&pad(&rooms, CELL_WIDTH).gray());
+        }
+
+        println!("{separator}");
+        println!("{title_line}");
+        println!("{room_line}");
+    }
+}
+
+pub async fn timetable_records(
+    client: &Client,
+    timetable: &HashMap<String, String>,
+) -> Result<Vec<TimetableRecord>> {
+    let mut entries = client.get_timetable().await?;
+    entries.sort_by_key(|entry| (entry.weekday.num_days_from_monday(), entry.period));
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| TimetableRecord::from_entry(&entry, period_time(timetable, entry.period)))
+        .collect())
+}
+
+/// Find the configured time of a period, with keys such as `1` or `1限`
+pub fn period_time(timetable: &HashMap<String, String>, period: u32) -> Option<&String> {
+    timetable
+        .iter()
+        .find(|(key, _)| parse_period(key) == Some(period))
+        .map(|(_, time)| time)
+}
+
+/// Parse the start and end of a period time such as `9:00 ~ 10:35`
+pub fn period_range(
+    timetable: &HashMap<String, String>,
+    period: u32,
+) -> Option<(NaiveTime, NaiveTime)> {
+    parse_time_range(period_time(timetable, period)?)
+}
+
+pub fn parse_time_range(time: &str) -> Option<(NaiveTime, NaiveTime)> {
+    let (start, end) = time.split_once('~')?;
+
+    Some((
+        NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?,
+        NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?,
+    ))
+}
+
+pub fn parse_period(key: &str) -> Option<u32> {
+    key.chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+pub fn weekday_label(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "月",
+        Weekday::Tue => "火",
+        Weekday::Wed => "水",
+        Weekday::Thu => "木",
+        Weekday::Fri => "金",
+        Weekday::Sat => "土",
+        Weekday::Sun => "日",
+    }
+}
+
+/// Truncate or pad `text` to exactly `width` terminal columns
+fn pad(text: &str, width: usize) -> String {
+    let mut s = String::new();
+    let mut used = 0;
+
+    for c in text.chars() {
+        let c_width = c.width().unwrap_or(0);
+        if used + c_width > width {
+            break;
+        }
+        s.push(c);
+        used += c_width;
+    }
+
+    // The width of a string can exceed the sum of its characters, e.g. for control characters
+    s.push_str(&" ".repeat(width.saturating_sub(s.width())));
+    s
+}
+
+fn print_period_times(timetable: &HashMap<String, String>) {
     if timetable.is_empty() {
         println!("Timetable is empty");
         return;
@@ -34,3 +202,16 @@ pub fn timetable(timetable: &HashMap<String, String>) {
     println!("==== TIMETABLE ====");
     println!("{s}");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pad_test() {
+        assert_eq!(pad("OS", 4), "OS  ");
+        assert_eq!(pad("データ構造", 5), "デー ");
+        assert_eq!(pad("Operating Systems", 9), "Operating");
+        assert!(pad("a\u{7}b\u{7}c", 3).starts_with('a'));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    cache,
+    cmd::{
+        INDENT, colorize,
+        timetable::{period_time, weekday_label},
+    },
+    color::AppColorize as _,
+    courses,
+    error::Result,
+    output::{AssignmentRecord, OutputFormat, TimetableRecord, print_records},
+    timezone,
+};
+use chrono::Datelike as _;
+use manaba_sdk::{
+    Client,
+    assignment::AssignmentImportanceLevel,
+    query::{AssignmentState, Queryable as _},
+};
+use std::{collections::HashMap, time::Duration};
+
+const CACHE_MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
+pub async fn today(
+    client: &Client,
+    timetable: &HashMap<String, String>,
+    format: OutputFormat,
+) -> Result<()> {
+    let now = timezone::institution_now();
+
+    let mut entries = client
+        .get_timetable()
+        .await?
+        .into_iter()
+        .filter(|entry| entry.weekday == now.weekday())
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.period);
+
+    if format != OutputFormat::Text {
+        let records = entries
+            .iter()
+            .map(|entry| TimetableRecord::from_entry(entry, period_time(timetable, entry.period)))
+            .collect::<Vec<_>>();
+        return print_records(&records, format);
+    }
+
+    let header = format!(
+        " {} ({}) ",
+        now.format("%m/%d"),
+        weekday_label(now.weekday())
+    );
+    println!("{}", header.black().on_blue().with_bold());
+
+    if entries.is_empty() {
+        println!("{INDENT}No classes today");
+        return Ok(());
+    }
+
+    let assignments = match cache::load(CACHE_MAX_AGE) {
+        Some(assignments) => assignments,
+        None => cache::refresh(client).await?,
+    };
+
+    for entry in &entries {
+        let time = period_time(timetable, entry.period).map_or("", String::as_str);
+        let room = entry
+            .room
+            .as_ref()
+            .map_or(String::new(), |room| format!(" @ {room}"));
+
+        println!(
+            "{INDENT}{} {}  {}{}",
+            format!("{}限", entry.period).with_bold(),
+            time,
+            courses::short_title(&entry.course),
+            room.gray()
+        );
+
+        for assignment in assignments
+            .iter()
+            .filter(|assignment| assignment.course_id == entry.course.id && is_open(assignment))
+        {
+            let due_date = assignment
+                .due_date
+                .map_or(String::new(), |d| d.format("%m/%d %H:%M").to_string());
+            let content = format!("{INDENT}{INDENT}{} (締切: {due_date})", assignment.title);
+
+            println!(
+                "{}",
+                colorize(
+                    content,
+                    &assignment.receptible_state,
+                    &assignment
+                        .importance
+                        .unwrap_or(AssignmentImportanceLevel::None)
+                )
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn is_open(assignment: &AssignmentRecord) -> bool {
+    assignment.state() == AssignmentState::Todo
+}
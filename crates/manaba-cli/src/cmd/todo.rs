@@ -0,0 +1,187 @@
+use crate::color::AppColorize as _;
+use crate::{
+    APP_CONFIG, client,
+    cmd::{
+        INDENT, TodoAction, colorize, colorize_bg, filter::AssignmentFilter, local_label,
+        note_line, state_label,
+    },
+    courses::{self, normalize_id},
+    error::{Error, Result},
+    output::{AssignmentRecord, OutputFormat, print_records},
+    overlay,
+    timezone::TimeArg,
+};
+use manaba_sdk::{
+    assignment::{AssignmentImportanceLevel, AssignmentKind, AssignmentSubmitState},
+    query::Queryable as _,
+    todo::TodoList,
+};
+use std::path::PathBuf;
+
+pub fn todo_path() -> PathBuf {
+    TodoList::default_path().unwrap()
+}
+
+pub async fn edit_todos(action: TodoAction) -> Result<()> {
+    let path = todo_path();
+    let mut todos = TodoList::load(&path)?;
+
+    match action {
+        TodoAction::Add { title, course, due } => {
+            let course = match course {
+                Some(pattern) => Some(find_course(&pattern).await?),
+                None => None,
+            };
+
+            let due = due.map(TimeArg::resolve).transpose()?;
+            let todo = todos.add(title, course, due);
+            println!("Added {}: {}", todo.id, todo.title);
+        }
+        TodoAction::Done { id } => {
+            let todo = todos.get_mut(&id).ok_or(Error::TodoNotFound(id))?;
+            todo.done = true;
+            println!("Done {}: {}", todo.id, todo.title);
+        }
+        TodoAction::Undone { id } => {
+            let todo = todos.get_mut(&id).ok_or(Error::TodoNotFound(id))?;
+            todo.done = false;
+            println!("Reopened {}: {}", todo.id, todo.title);
+        }
+        TodoAction::Remove { id } => {
+            let todo = todos.remove(&id).ok_or(Error::TodoNotFound(id))?;
+            println!("Removed {}: {}", todo.id, todo.title);
+        }
+    }
+
+    todos.save(&path)?;
+    Ok(())
+}
+
+/// List every todo, including finished ones
+pub fn list_todos(format: OutputFormat) -> Result<()> {
+    let records = todo_records(&AssignmentFilter::all())?;
+
+    if format != OutputFormat::Text {
+        return print_records(&records, format);
+    }
+
+    if records.is_empty() {
+        println!("No todos");
+        return Ok(());
+    }
+
+    for record in &records {
+        let check = if record.submit_state == AssignmentSubmitState::Done {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+        let due_date = record.due_date.map_or(String::new(), |due_date| {
+            due_date.format("%Y-%m-%d %H:%M").to_string()
+        });
+        let course = if record.course_title.is_empty() {
+            String::new()
+        } else {
+            format!("({})", record.course_title).gray()
+        };
+
+        let line = format!(
+            "{INDENT}{check} {}  {}  {due_date}  {course}",
+            record.id.gray(),
+            record.title
+        );
+        println!("{}", line.trim_end());
+    }
+
+    Ok(())
+}
+
+/// Todos shown by `filter`, in the order they were added
+pub fn todo_records(filter: &AssignmentFilter) -> Result<Vec<AssignmentRecord>> {
+    if !filter.query.includes_kind(AssignmentKind::Todo) {
+        return Ok(Vec::new());
+    }
+
+    let todos = TodoList::load(&todo_path())?;
+
+    Ok(todos
+        .iter()
+        .filter(|todo| filter.is_visible(*todo))
+        .map(AssignmentRecord::from_todo)
+        .collect())
+}
+
+/// Todo section of `manaba check`
+pub fn todo(filter: &AssignmentFilter, mut records: Vec<AssignmentRecord>) {
+    filter.query.sort(&mut records);
+
+    for record in &records {
+        let importance = record.importance.unwrap_or(AssignmentImportanceLevel::None);
+        let local = overlay::local_state(&record.id);
+
+        let status_str = if filter.shows_states() {
+            state_label(record.state())
+        } else {
+            colorize_bg(" ", &record.receptible_state, &importance)
+        };
+        println!(
+            "{INDENT}{}{}{}",
+            status_str,
+            format!(" {} ", record.title).on_black(),
+            local_label(local)
+        );
+
+        let due_date_str = record.due_date.map_or(String::new(), |due_date| {
+            due_date.format("%Y-%m-%d %H:%M").to_string()
+        });
+        let course_str = if record.course_title.is_empty() {
+            String::new()
+        } else {
+            format!("\n{INDENT}{INDENT}コース: {}", record.course_title)
+        };
+        println!(
+            "{}",
+            colorize(
+                format!(
+                    "{INDENT}{INDENT}締切: {due_date_str}{course_str}{}",
+                    note_line(local)
+                ),
+                &record.receptible_state,
+                &importance
+            )
+        );
+    }
+
+    println!();
+}
+
+/// Find a course by id, or by a case-insensitive part of its title or alias
+async fn find_course(pattern: &str) -> Result<(String, String)> {
+    let app_config = APP_CONFIG.get().unwrap();
+    let client = client(app_config).await?;
+    let courses = courses::courses(&client).await?;
+
+    let id = normalize_id(pattern);
+    if let Some(course) = courses.iter().find(|course| course.id == id) {
+        return Ok((course.id.clone(), course.title.clone()));
+    }
+
+    let pattern_lower = pattern.to_lowercase();
+    let matches = courses
+        .iter()
+        .filter(|course| course.title.to_lowercase().contains(&pattern_lower))
+        .collect::<Vec<_>>();
+
+    match matches.as_slice() {
+        [course] => Ok((course.id.clone(), course.title.clone())),
+        [] => Err(Error::CourseNotFound(pattern.to_owned())),
+        _ => Err(Error::AmbiguousCourse {
+            pattern: pattern.to_owned(),
+            candidates: matches
+                .iter()
+                .map(|course| course.title.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        }),
+    }
+}
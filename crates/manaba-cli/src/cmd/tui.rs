@@ -0,0 +1,499 @@
+use crate::{
+    cache, color::APP_COLOR, error::Result, output::AssignmentRecord, overlay, timezone, urgency,
+};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use manaba_sdk::{
+    Client,
+    assignment::{AssignmentImportanceLevel, AssignmentReceptibleState, AssignmentSubmitState},
+    overlay::{LocalState, LocalStatus, Overlay},
+};
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Courses,
+    Assignments,
+}
+
+#[derive(Clone, Copy)]
+enum StateFilter {
+    Open,
+    Done,
+    All,
+}
+
+impl StateFilter {
+    fn next(self) -> Self {
+        match self {
+            StateFilter::Open => StateFilter::Done,
+            StateFilter::Done => StateFilter::All,
+            StateFilter::All => StateFilter::Open,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            StateFilter::Open => "open",
+            StateFilter::Done => "done",
+            StateFilter::All => "all",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ImportanceFilter {
+    Any,
+    Medium,
+    High,
+}
+
+impl ImportanceFilter {
+    fn next(self) -> Self {
+        match self {
+            ImportanceFilter::Any => ImportanceFilter::Medium,
+            ImportanceFilter::Medium => ImportanceFilter::High,
+            ImportanceFilter::High => ImportanceFilter::Any,
+        }
+    }
+
+    fn label(self) -> String {
+        match self {
+            ImportanceFilter::Any => "any".to_owned(),
+            ImportanceFilter::Medium => format!("within {}", urgency::medium_reach()),
+            ImportanceFilter::High => "urgent".to_owned(),
+        }
+    }
+}
+
+struct App {
+    base_url: String,
+    assignments: Vec<AssignmentRecord>,
+    courses: Vec<(String, String)>,
+    overlay: Overlay,
+    focus: Pane,
+    course_state: ListState,
+    assignment_state: ListState,
+    state_filter: StateFilter,
+    importance_filter: ImportanceFilter,
+    status: String,
+}
+
+impl App {
+    fn set_assignments(&mut self, mut assignments: Vec<AssignmentRecord>) {
+        assignments.sort_by_key(|assignment| assignment.due_date);
+
+        let mut courses = assignments
+            .iter()
+            .map(|assignment| {
+                let title = if assignment.course_id.is_empty() {
+                    // Todos without a course
+                    "Todo".to_owned()
+                } else {
+                    assignment.course_title.clone()
+                };
+                (assignment.course_id.clone(), title)
+            })
+            .collect::<Vec<_>>();
+        courses.sort();
+        courses.dedup();
+
+        self.assignments = assignments;
+        self.courses = courses;
+        self.clamp_selection();
+    }
+
+    fn local_state(&self, assignment: &AssignmentRecord) -> Option<&LocalState> {
+        self.overlay.get(&assignment.id)
+    }
+
+    /// Submitted, or marked as done offline or ignored with `d` or `manaba mark`
+    fn is_done(&self, assignment: &AssignmentRecord) -> bool {
+        assignment.submit_state == AssignmentSubmitState::Done
+            || self.local_state(assignment).is_some_and(|local| {
+                matches!(
+                    local.status,
+                    Some(LocalStatus::DoneOffline | LocalStatus::Ignored)
+                )
+            })
+    }
+
+    /// Toggle the done offline mark of an assignment, keeping its note
+    fn toggle_done(&mut self, id: &str) -> Result<()> {
+        let mut local = self.overlay.get(id).cloned().unwrap_or_default();
+        local.status = match local.status {
+            Some(LocalStatus::DoneOffline) => None,
+            _ => Some(LocalStatus::DoneOffline),
+        };
+        self.overlay.set(id, local);
+        self.overlay.save(&overlay::overlay_path())?;
+        Ok(())
+    }
+
+    /// Course id selected in the course pane, where the first row means all courses
+    fn selected_course(&self) -> Option<&str> {
+        match self.course_state.selected() {
+            Some(0) | None => None,
+            Some(i) => self.courses.get(i - 1).map(|(id, _)| id.as_str()),
+        }
+    }
+
+    fn visible_assignments(&self) -> Vec<&AssignmentRecord> {
+        let course = self.selected_course();
+
+        self.assignments
+            .iter()
+            .filter(|assignment| course.is_none_or(|course| assignment.course_id == course))
+            .filter(|assignment| match self.state_filter {
+                StateFilter::Open => {
+                    !self.is_done(assignment)
+                        && assignment.receptible_state == AssignmentReceptibleState::Open
+                }
+                StateFilter::Done => self.is_done(assignment),
+                StateFilter::All => true,
+            })
+            .filter(|assignment| match self.importance_filter {
+                ImportanceFilter::Any => true,
+                ImportanceFilter::Medium => assignment.importance.is_some_and(|level| {
+                    urgency::is_urgent(level) || level == AssignmentImportanceLevel::Medium
+                }),
+                ImportanceFilter::High => assignment.importance.is_some_and(urgency::is_urgent),
+            })
+            .collect()
+    }
+
+    fn selected_assignment(&self) -> Option<&AssignmentRecord> {
+        let index = self.assignment_state.selected()?;
+        self.visible_assignments().get(index).copied()
+    }
+
+    fn clamp_selection(&mut self) {
+        let course_count = self.courses.len() + 1;
+        if self
+            .course_state
+            .selected()
+            .is_none_or(|i| i >= course_count)
+        {
+            self.course_state.select(Some(0));
+        }
+
+        let assignment_count = self.visible_assignments().len();
+        match self.assignment_state.selected() {
+            _ if assignment_count == 0 => self.assignment_state.select(None),
+            Some(i) if i < assignment_count => {}
+            _ => self.assignment_state.select(Some(0)),
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let (state, count) = match self.focus {
+            Pane::Courses => (&mut self.course_state, self.courses.len() + 1),
+            Pane::Assignments => {
+                let count = self.visible_assignments().len();
+                (&mut self.assignment_state, count)
+            }
+        };
+
+        if count == 0 {
+            return;
+        }
+
+        let current = state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, count as isize - 1);
+        state.select(Some(next as usize));
+
+        if self.focus == Pane::Courses {
+            self.assignment_state.select(Some(0));
+        }
+        self.clamp_selection();
+    }
+}
+
+pub async fn tui(client: Client, refresh_minutes: u64) -> Result<()> {
+    let (refresh_tx, mut refresh_rx) = mpsc::unbounded_channel::<()>();
+    let (result_tx, mut result_rx) = mpsc::unbounded_channel();
+
+    // Refresh in the background so the UI stays responsive while manaba is slow
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(refresh_minutes.max(1) * 60));
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                Some(()) = refresh_rx.recv() => {}
+            }
+
+            let result = cache::refresh(&client).await.map_err(|e| e.to_string());
+            if result_tx.send(result).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut app = App {
+        base_url: crate::APP_CONFIG.get().unwrap().base_url.clone(),
+        assignments: Vec::new(),
+        courses: Vec::new(),
+        overlay: overlay::load()?,
+        focus: Pane::Assignments,
+        course_state: ListState::default(),
+        assignment_state: ListState::default(),
+        state_filter: StateFilter::Open,
+        importance_filter: ImportanceFilter::Any,
+        status: "Loading...".to_owned(),
+    };
+    app.set_assignments(cache::load_stale().unwrap_or_default());
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app, &refresh_tx, &mut result_rx);
+    ratatui::restore();
+
+    result
+}
+
+fn run(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    refresh_tx: &mpsc::UnboundedSender<()>,
+    result_rx: &mut mpsc::UnboundedReceiver<std::result::Result<Vec<AssignmentRecord>, String>>,
+) -> Result<()> {
+    loop {
+        while let Ok(result) = result_rx.try_recv() {
+            match result {
+                Ok(assignments) => {
+                    app.status = format!("Updated at {}", timezone::now().format("%H:%M"));
+                    app.set_assignments(assignments);
+                }
+                Err(e) => app.status = format!("Failed to refresh: {e}"),
+            }
+        }
+
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if !event::poll(POLL_INTERVAL)? {
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right => {
+                app.focus = match app.focus {
+                    Pane::Courses => Pane::Assignments,
+                    Pane::Assignments => Pane::Courses,
+                };
+            }
+            KeyCode::Char('h') => app.focus = Pane::Courses,
+            KeyCode::Char('l') => app.focus = Pane::Assignments,
+            KeyCode::Down | KeyCode::Char('j') => app.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => app.move_selection(-1),
+            KeyCode::PageDown => app.move_selection(10),
+            KeyCode::PageUp => app.move_selection(-10),
+            KeyCode::Char('f') => {
+                app.state_filter = app.state_filter.next();
+                app.clamp_selection();
+            }
+            KeyCode::Char('i') => {
+                app.importance_filter = app.importance_filter.next();
+                app.clamp_selection();
+            }
+            KeyCode::Char('r') => {
+                app.status = "Refreshing...".to_owned();
+                let _ = refresh_tx.send(());
+            }
+            KeyCode::Char('o') | KeyCode::Enter => {
+                let url = match (app.focus, app.selected_course()) {
+                    (Pane::Courses, Some(course)) => Some(format!("{}/{}", app.base_url, course)),
+                    (Pane::Courses, None) => Some(app.base_url.clone()),
+                    (Pane::Assignments, _) => app
+                        .selected_assignment()
+                        .map(|assignment| assignment.url(&app.base_url)),
+                };
+
+                if let Some(url) = url
+                    && let Err(e) = opener::open(&url)
+                {
+                    app.status = format!("Failed to open browser: {e}");
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(id) = app.selected_assignment().map(|a| a.id.clone()) {
+                    if let Err(e) = app.toggle_done(&id) {
+                        app.status = format!("Failed to save the mark: {e}");
+                    }
+                    app.clamp_selection();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn hex_color(hex: &str) -> Color {
+    let hex = hex.trim_start_matches('#');
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+            .unwrap_or(0)
+    };
+
+    Color::Rgb(channel(0), channel(2), channel(4))
+}
+
+fn importance_color(assignment: &AssignmentRecord, is_done: bool) -> Color {
+    let app_color = APP_COLOR.get_or_init(Default::default);
+
+    if is_done || assignment.receptible_state != AssignmentReceptibleState::Open {
+        return hex_color(&app_color.gray);
+    }
+
+    match assignment.importance {
+        Some(
+            AssignmentImportanceLevel::High
+            | AssignmentImportanceLevel::DueToday
+            | AssignmentImportanceLevel::Overdue,
+        ) => hex_color(&app_color.red),
+        Some(AssignmentImportanceLevel::Medium) => hex_color(&app_color.yellow),
+        Some(AssignmentImportanceLevel::Low) => hex_color(&app_color.aqua),
+        _ => Color::Reset,
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let [main, details, footer] = Layout::vertical([
+        Constraint::Min(5),
+        Constraint::Length(7),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let [courses_area, assignments_area] =
+        Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)]).areas(main);
+
+    let border_style = |pane: Pane| {
+        if app.focus == pane {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().add_modifier(Modifier::DIM)
+        }
+    };
+    let highlight_style = Style::default().add_modifier(Modifier::REVERSED);
+
+    let course_items = std::iter::once(ListItem::new("All courses"))
+        .chain(
+            app.courses
+                .iter()
+                .map(|(_, title)| ListItem::new(title.as_str())),
+        )
+        .collect::<Vec<_>>();
+    let courses = List::new(course_items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Courses ")
+                .border_style(border_style(Pane::Courses)),
+        )
+        .highlight_style(highlight_style);
+    frame.render_stateful_widget(courses, courses_area, &mut app.course_state);
+
+    let assignment_items = app
+        .visible_assignments()
+        .into_iter()
+        .map(|assignment| {
+            let is_done = app.is_done(assignment);
+            let due_date = assignment.due_date.map_or("     -     ".to_owned(), |d| {
+                d.format("%m/%d %H:%M").to_string()
+            });
+            let mark = if is_done { "✓" } else { " " };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{mark} {due_date} "),
+                    Style::default().fg(importance_color(assignment, is_done)),
+                ),
+                Span::raw(assignment.title.clone()),
+            ]))
+        })
+        .collect::<Vec<_>>();
+    let title = format!(
+        " Assignments [{}] [{}] ",
+        app.state_filter.label(),
+        app.importance_filter.label()
+    );
+    let assignments = List::new(assignment_items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(border_style(Pane::Assignments)),
+        )
+        .highlight_style(highlight_style);
+    frame.render_stateful_widget(assignments, assignments_area, &mut app.assignment_state);
+
+    let details_text = match app.selected_assignment() {
+        Some(assignment) => {
+            let format_date = |date: Option<chrono::DateTime<chrono::FixedOffset>>| {
+                date.map_or(String::new(), |d| d.format("%Y-%m-%d %H:%M").to_string())
+            };
+            let local = app.local_state(assignment);
+            let state = match local.and_then(|local| local.status) {
+                Some(status) => format!("{status:?} (local)"),
+                None => format!(
+                    "{:?} / {:?}",
+                    assignment.submit_state, assignment.receptible_state
+                ),
+            };
+
+            vec![
+                Line::from(Span::styled(
+                    assignment.title.as_str(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Line::from(format!(
+                    "{} ({:?})",
+                    assignment.course_title, assignment.kind
+                )),
+                Line::from(format!("状態: {state}")),
+                Line::from(format!(
+                    "開始: {}  締切: {}",
+                    format_date(assignment.start_date),
+                    format_date(assignment.due_date)
+                )),
+                Line::from(format!(
+                    "メモ: {}",
+                    local.and_then(|local| local.note.as_deref()).unwrap_or("")
+                )),
+                Line::from(assignment.url(&app.base_url)),
+            ]
+        }
+        None => vec![Line::from("No assignment selected")],
+    };
+    let details_widget = Paragraph::new(details_text)
+        .block(Block::default().borders(Borders::ALL).title(" Details "))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(details_widget, details);
+
+    let help = format!(
+        " {} | tab: switch pane  j/k: move  o: open  d: mark done  f: state  i: urgency  r: refresh  q: quit",
+        app.status
+    );
+    frame.render_widget(
+        Paragraph::new(help).style(Style::default().add_modifier(Modifier::DIM)),
+        footer,
+    );
+}
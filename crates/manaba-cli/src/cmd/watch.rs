@@ -0,0 +1,57 @@
+use crate::{
+    cache,
+    error::{Result, print_err},
+    notify::{Notifier, NotifyState},
+    timezone,
+};
+use manaba_sdk::Client;
+use std::time::Duration;
+
+pub async fn watch(
+    client: &Client,
+    notifier: &Notifier<'_>,
+    lead_hours: &[u64],
+    interval_minutes: u64,
+    once: bool,
+) -> Result<()> {
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_minutes.max(1) * 60));
+    // Kept across ticks, so that a dry run does not report the same alerts again
+    let mut state = NotifyState::load();
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = check(client, notifier, &mut state, lead_hours).await {
+            if once {
+                return Err(e);
+            }
+            print_err(format!("Failed to check assignments: {e}"));
+        }
+
+        if once {
+            return Ok(());
+        }
+    }
+}
+
+async fn check(
+    client: &Client,
+    notifier: &Notifier<'_>,
+    state: &mut NotifyState,
+    lead_hours: &[u64],
+) -> Result<()> {
+    let assignments = cache::refresh(client).await?;
+
+    let events = state.detect(&assignments, lead_hours, timezone::now());
+
+    for event in &events {
+        notifier.notify(event).await;
+    }
+
+    // A dry run must not record alerts as fired on disk
+    if notifier.is_dry_run() {
+        return Ok(());
+    }
+
+    state.save()
+}
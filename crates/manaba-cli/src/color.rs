@@ -5,6 +5,17 @@ use std::{fmt, sync::OnceLock};
 
 pub static APP_COLOR: OnceLock<AppColor> = OnceLock::new();
 
+/// Keys of `[color]`
+pub const COLOR_NAMES: [&str; 8] = [
+    "white", "black", "red", "blue", "aqua", "yellow", "green", "gray",
+];
+
+/// Whether `color` is a hex color such as `D3C6AA` or `#D3C6AA`
+pub fn is_hex_color(color: &str) -> bool {
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 pub struct AppColor {
     pub white: String,
     pub black: String,
@@ -0,0 +1,102 @@
+use crate::{
+    APP_CONFIG,
+    app_config::CoursePrefs,
+    color::{APP_COLOR, AppColorize as _},
+    error::Result,
+    term::{APP_TERM, TermFilter},
+};
+use manaba_sdk::{Client, Course};
+
+/// Fetch courses in the term given by `--term`, applying `[courses]` preferences
+///
+/// Hidden courses are dropped, aliases replace titles and pinned courses come first.
+pub async fn courses(client: &Client) -> Result<Vec<Course>> {
+    let filter = APP_TERM.get().copied().unwrap_or(TermFilter::All);
+
+    let mut courses = client
+        .get_courses()
+        .await?
+        .into_iter()
+        .filter(|course| filter.matches(course))
+        .filter(|course| !prefs(&course.id).is_some_and(|prefs| prefs.hidden))
+        .map(|mut course| {
+            if let Some(alias) = prefs(&course.id).and_then(|prefs| prefs.alias.clone()) {
+                course.title = alias;
+            }
+            course
+        })
+        .collect::<Vec<_>>();
+
+    courses.sort_by_key(|course| !prefs(&course.id).is_some_and(|prefs| prefs.pinned));
+
+    Ok(courses)
+}
+
+pub fn prefs(course_id: &str) -> Option<&'static CoursePrefs> {
+    let app_config = APP_CONFIG.get().unwrap();
+    app_config.courses.get(course_id)
+}
+
+/// Hex color of the course from `[courses]`, either a name in `[color]` or a hex value
+pub fn color(course_id: &str) -> Option<String> {
+    let color = prefs(course_id)?.color.as_deref()?;
+    let app_color = APP_COLOR.get_or_init(Default::default);
+
+    let hex = match color {
+        "white" => &app_color.white,
+        "black" => &app_color.black,
+        "red" => &app_color.red,
+        "blue" => &app_color.blue,
+        "aqua" => &app_color.aqua,
+        "yellow" => &app_color.yellow,
+        "green" => &app_color.green,
+        "gray" => &app_color.gray,
+        hex => hex.trim_start_matches('#'),
+    };
+
+    Some(hex.to_owned())
+}
+
+/// Course title heading the assignments of the course
+pub fn header(course: &Course) -> String {
+    let title = course.title.black();
+
+    match color(&course.id) {
+        Some(hex) => colored_text::Colorize::bold(&colored_text::Colorize::on_hex(&title, &hex)),
+        None => title.on_blue().with_bold(),
+    }
+}
+
+/// Course title in the color from `[courses]`, if any
+pub fn colored_title(course: &Course) -> String {
+    match color(&course.id) {
+        Some(hex) => colored_text::Colorize::hex(&course.title, &hex),
+        None => course.title.clone(),
+    }
+}
+
+/// Title for timetable views, the alias or the title without the course code
+pub fn short_title(course: &Course) -> String {
+    if let Some(alias) = prefs(&course.id).and_then(|prefs| prefs.alias.as_ref()) {
+        return alias.clone();
+    }
+
+    strip_code(&course.title).to_owned()
+}
+
+/// `Operating Systems` from `12345:Operating Systems`
+pub fn strip_code(title: &str) -> &str {
+    match title.split_once(':') {
+        Some((code, rest)) if code.chars().all(|c| c.is_ascii_digit()) => rest.trim(),
+        _ => title,
+    }
+}
+
+/// Accept `course_12345` as well as the bare `12345`
+pub fn normalize_id(course: &str) -> String {
+    if course.chars().all(|c| c.is_ascii_digit()) {
+        format!("course_{course}")
+    } else {
+        course.to_owned()
+    }
+}
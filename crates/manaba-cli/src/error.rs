@@ -1,8 +1,21 @@
 use colored_text::Colorize as _;
+use manaba_sdk::error::ManabaError;
 use std::{fmt::Display, path::PathBuf};
 
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
+// Exit codes for scripts and cron jobs, `2` is used by clap for usage errors
+pub const EXIT_FAILURE: u8 = 1;
+/// `manaba check` listed open assignments that are urgent
+pub const EXIT_URGENT: u8 = 3;
+pub const EXIT_AUTH: u8 = 4;
+pub const EXIT_NETWORK: u8 = 5;
+pub const EXIT_PARSE: u8 = 6;
+/// `manaba status` found assignments due within a week, but none urgent
+pub const EXIT_MEDIUM: u8 = 7;
+/// Marks the block as urgent in i3blocks, which treats other non-zero codes as errors
+pub const EXIT_BAR_URGENT: u8 = 33;
+
 pub fn print_err<T: AsRef<str> + Display>(e: T) {
     eprintln!("{}", e.red());
 }
@@ -39,4 +52,103 @@ pub enum Error {
 
     #[error("{0}")]
     Dialoguer(#[from] dialoguer::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("{0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("{0}")]
+    Notification(#[from] notify_rust::error::Error),
+
+    #[error("{0}")]
+    Smtp(#[from] lettre::transport::smtp::Error),
+
+    #[error("{0}")]
+    Email(#[from] lettre::error::Error),
+
+    #[error("Invalid email address: {0}")]
+    EmailAddress(#[from] lettre::address::AddressError),
+
+    #[error("`[{0}]` is not configured in the config file")]
+    MissingConfig(&'static str),
+
+    #[error("Webhook request failed: {0}")]
+    Webhook(String),
+
+    #[error("Invalid template: {0}")]
+    Template(String),
+
+    #[error("{0}")]
+    TomlEdit(#[from] toml_edit::TomlError),
+
+    #[error("{0}")]
+    TomlSerialize(#[from] toml::ser::Error),
+
+    #[error("Failed to edit config file: {0}")]
+    ConfigEdit(String),
+
+    #[error("No course matches `{0}`")]
+    CourseNotFound(String),
+
+    #[error("`{pattern}` matches several courses: {candidates}")]
+    AmbiguousCourse { pattern: String, candidates: String },
+
+    #[error("Todo not found: {0}")]
+    TodoNotFound(String),
+
+    #[error("Nothing matches `{0}`")]
+    NoMatch(String),
+
+    #[error("Failed to run `{command}`: {source}")]
+    Finder {
+        command: String,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to run editor `{command}`: {source}")]
+    Editor {
+        command: String,
+        source: std::io::Error,
+    },
+
+    #[error("No config key `{0}`")]
+    ConfigKeyNotFound(String),
+
+    #[error("Config file has {0} problem(s)")]
+    InvalidConfig(usize),
+
+    #[error("Config file already exists at {0}, use --force to overwrite it")]
+    ConfigExists(PathBuf),
+
+    #[error("`{0}` does not exist in the display time zone")]
+    InvalidTime(String),
+
+    #[error("Cannot {0} without a terminal")]
+    NonInteractive(&'static str),
+
+    #[error("Cannot find the data directory")]
+    DataDirNotFound,
+}
+
+impl Error {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Manaba(ManabaError::InvalidCookie | ManabaError::LoadCookie(_)) => EXIT_AUTH,
+            Error::Manaba(
+                ManabaError::SendRequestError(_) | ManabaError::HtmlBodyParseError(_),
+            )
+            | Error::Webhook(_)
+            | Error::Smtp(_) => EXIT_NETWORK,
+            Error::Manaba(ManabaError::ScrapeError(_))
+            | Error::ConfigFileDeserialize { .. }
+            | Error::InvalidConfig(_)
+            | Error::TomlEdit(_) => EXIT_PARSE,
+            _ => EXIT_FAILURE,
+        }
+    }
 }
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Utc};
+
+/// UTC date-time form, so calendar clients convert it to their own zone
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+pub struct IcsEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: String,
+    pub url: String,
+    pub due: DateTime<Utc>,
+}
+
+pub fn render_calendar(events: &[IcsEvent]) -> String {
+    let stamp = Utc::now().format(DATE_TIME_FORMAT).to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        "PRODID:-//crcrworks//manaba-cli//EN".to_owned(),
+        "CALSCALE:GREGORIAN".to_owned(),
+        "METHOD:PUBLISH".to_owned(),
+        "X-WR-CALNAME:manaba".to_owned(),
+    ];
+
+    for event in events {
+        let due = event.due.format(DATE_TIME_FORMAT);
+
+        lines.push("BEGIN:VEVENT".to_owned());
+        lines.push(format!("UID:{}", escape_text(&event.uid)));
+        lines.push(format!("DTSTAMP:{stamp}"));
+        lines.push(format!("DTSTART:{due}"));
+        lines.push(format!("DTEND:{due}"));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&event.description)));
+        lines.push(format!("URL:{}", event.url));
+        lines.push("END:VEVENT".to_owned());
+    }
+
+    lines.push("END:VCALENDAR".to_owned());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Fold content lines longer than 75 octets (RFC 5545, section 3.1)
+fn fold_line(line: &str) -> String {
+    const MAX_OCTETS: usize = 75;
+
+    let mut folded = String::new();
+    let mut octets = 0;
+
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone as _};
+
+    fn event(summary: &str, description: &str) -> IcsEvent {
+        IcsEvent {
+            uid: "course_11111_report_22222@manaba".to_owned(),
+            summary: summary.to_owned(),
+            description: description.to_owned(),
+            url: "https://manaba.example.com/ct/course_11111_report_22222".to_owned(),
+            due: FixedOffset::east_opt(9 * 3600)
+                .unwrap()
+                .with_ymd_and_hms(2026, 10, 20, 23, 59, 0)
+                .unwrap()
+                .to_utc(),
+        }
+    }
+
+    /// Content lines with folding undone
+    fn unfold(calendar: &str) -> Vec<String> {
+        calendar
+            .replace("\r\n ", "")
+            .split("\r\n")
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[test]
+    fn fold_line_test() {
+        let line = "a".repeat(160);
+        let folded = fold_line(&line);
+        let parts = folded.split("\r\n").collect::<Vec<_>>();
+
+        assert_eq!(
+            parts.iter().map(|part| part.len()).collect::<Vec<_>>(),
+            [75, 75, 12]
+        );
+        assert!(parts[1..].iter().all(|part| part.starts_with(' ')));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+
+        assert_eq!(fold_line(&"a".repeat(75)), "a".repeat(75));
+    }
+
+    #[test]
+    fn fold_line_multibyte_test() {
+        // 3 octets each, so 25 fit on the first line and the rest never split a character
+        let line = "課".repeat(40);
+        let folded = fold_line(&line);
+        let parts = folded.split("\r\n").collect::<Vec<_>>();
+
+        assert_eq!(
+            parts.iter().map(|part| part.len()).collect::<Vec<_>>(),
+            [75, 46]
+        );
+        assert!(parts.iter().all(|part| part.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn escape_text_test() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+    }
+
+    #[test]
+    fn render_calendar_test() {
+        let calendar = render_calendar(&[event(
+            "[Report] Essay; draft, v2",
+            "11111:Operating Systems\nRoom A\\B",
+        )]);
+
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert!(calendar.split("\r\n").all(|line| line.len() <= 75));
+
+        let lines = unfold(&calendar);
+        assert!(lines.contains(&"SUMMARY:[Report] Essay\\; draft\\, v2".to_owned()));
+        assert!(lines.contains(&"DESCRIPTION:11111:Operating Systems\\nRoom A\\\\B".to_owned()));
+        // 23:59 +09:00 in UTC, without a TZID
+        assert!(lines.contains(&"DTSTART:20261020T145900Z".to_owned()));
+        assert!(lines.contains(&"DTEND:20261020T145900Z".to_owned()));
+        assert!(!calendar.contains("TZID"));
+    }
+}
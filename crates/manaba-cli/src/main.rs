@@ -1,30 +1,58 @@
 mod app_config;
+mod cache;
 mod cmd;
 mod color;
+mod courses;
 mod error;
+mod ics;
+mod notify;
+mod output;
+mod overlay;
+mod picker;
+mod template;
+mod term;
+mod timezone;
+mod urgency;
 
 use app_config::AppConfig;
-use color::{APP_COLOR, AppColor};
+use clap::Parser as _;
+use color::{APP_COLOR, AppColor, is_hex_color};
 use config::Config;
 use dialoguer::Confirm;
 use error::{Error, Result, print_err};
-use manaba_sdk::{Client, Cookie, error::ManabaError};
-use std::{io::Write as _, path::PathBuf, sync::OnceLock};
+use manaba_sdk::{Client, Cookie, assignment::UrgencyPolicy, error::ManabaError};
+use std::{io::IsTerminal as _, path::PathBuf, process::ExitCode, sync::OnceLock};
 
 static APP_CONFIG: OnceLock<AppConfig> = OnceLock::new();
 static APP_CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
+/// Whether prompts may be shown, false with `--non-interactive` or when stdin is not a terminal
+static INTERACTIVE: OnceLock<bool> = OnceLock::new();
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    initialize_app_config();
-    initialize_app_color();
+async fn main() -> ExitCode {
+    cmd::complete();
+    let cli = cmd::Cli::parse();
+    INTERACTIVE.get_or_init(|| !cli.non_interactive && std::io::stdin().is_terminal());
+    APP_CONFIG_PATH.get_or_init(app_config_path);
+    if cli.needs_config() {
+        initialize_app_config();
+        initialize_app_color();
+    }
 
-    cmd::cmd().await?;
-    Ok(())
+    match cmd::cmd(cli).await {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            print_err(e.to_string());
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+fn is_interactive() -> bool {
+    INTERACTIVE.get().copied().unwrap_or(false)
 }
 
 fn initialize_app_config() {
-    APP_CONFIG_PATH.get_or_init(app_config_path);
     APP_CONFIG.get_or_init(|| match app_config() {
         Ok(app_config) => app_config,
         Err(e) => {
@@ -43,10 +71,10 @@ fn initialize_app_color() {
 
 fn handle_config_error(error: Error) -> AppConfig {
     match error {
-        Error::ConfigFileNotFound { .. } => {
-            create_config_file().unwrap_or_else(|_| AppConfig::default())
-        }
-        Error::ConfigFileDeserialize { .. } => AppConfig::default(),
+        // A file that exists but fails to parse is left for `manaba config validate` to explain
+        Error::ConfigFileNotFound {
+            ref config_path, ..
+        } if !config_path.exists() => create_config_file().unwrap_or_else(|_| AppConfig::default()),
         _ => AppConfig::default(),
     }
 }
@@ -60,7 +88,15 @@ fn create_app_color_from_config(
     macro_rules! set_color {
         ($field:ident) => {
             if let Some(color) = color_config.get(stringify!($field)) {
-                app_color.$field = color.clone();
+                if is_hex_color(color) {
+                    app_color.$field = color.trim_start_matches('#').to_owned();
+                } else {
+                    print_err(format!(
+                        "Ignoring invalid color `{}` for `{}`, expected a hex color such as D3C6AA",
+                        color,
+                        stringify!($field)
+                    ));
+                }
             }
         };
     }
@@ -94,12 +130,23 @@ fn app_config() -> Result<AppConfig> {
             config_path: APP_CONFIG_PATH.get().unwrap().to_owned(),
         })?;
 
-    config
-        .try_deserialize()
-        .map_err(|e| Error::ConfigFileDeserialize {
-            source: e,
-            config_path: APP_CONFIG_PATH.get().unwrap().to_owned(),
-        })
+    let mut app_config: AppConfig =
+        config
+            .try_deserialize()
+            .map_err(|e| Error::ConfigFileDeserialize {
+                source: e,
+                config_path: APP_CONFIG_PATH.get().unwrap().to_owned(),
+            })?;
+
+    // Bad thresholds only reset `[urgency]`, so the rest of the config still applies
+    if let Err(e) = app_config.urgency.validate() {
+        print_err(format!(
+            "Ignoring [urgency]: {e}. Please check it with `manaba config validate`"
+        ));
+        app_config.urgency = UrgencyPolicy::default();
+    }
+
+    Ok(app_config)
 }
 
 async fn client(app_config: &AppConfig) -> Result<Client> {
@@ -107,26 +154,24 @@ async fn client(app_config: &AppConfig) -> Result<Client> {
         let cookie = Cookie::load(&app_config.cookie_domain)?;
 
         match Client::new(&app_config.base_url, &cookie).await {
-            Ok(client) => return Ok(client),
-            Err(ManabaError::InvalidCookie) => {
+            Ok(client) => return Ok(client.with_timezone(app_config.timezone.institution)),
+            Err(ManabaError::InvalidCookie) if is_interactive() => {
                 let confirmation = Confirm::new()
                     .with_prompt("Cookie is invalid. Open manaba to load new Cookie?")
-                    .interact()
-                    .unwrap();
+                    .interact()?;
 
                 if !confirmation {
-                    std::process::exit(0);
+                    return Err(Error::from(ManabaError::InvalidCookie));
                 }
 
                 opener::open(&app_config.base_url)?;
 
                 let confirmation = Confirm::new()
                     .with_prompt("Load cookie? (Yes after opening manaba)")
-                    .interact()
-                    .unwrap();
+                    .interact()?;
 
                 if !confirmation {
-                    std::process::exit(0);
+                    return Err(Error::from(ManabaError::InvalidCookie));
                 }
 
                 continue;
@@ -137,35 +182,29 @@ async fn client(app_config: &AppConfig) -> Result<Client> {
 }
 
 fn create_config_file() -> Result<AppConfig, ()> {
+    if !is_interactive() {
+        return Err(());
+    }
+
     let confirmation = Confirm::new()
         .with_prompt("Config file not found. Do you want to create a new one?")
         .interact()
-        .unwrap();
+        .map_err(|e| print_err(e.to_string()))?;
 
     if confirmation {
         let path = APP_CONFIG_PATH.get().unwrap();
 
-        // Create parent directories if they don't exist
-        if let Some(parent) = path.parent() {
-            if let Err(e) = std::fs::create_dir_all(parent) {
-                print_err(format!("Failed to create config directory: {}", e));
-                return Err(());
+        match app_config::write_default_config(path) {
+            Err(e) => {
+                print_err(format!("Failed to create config file: {e}"));
+                Err(())
             }
-        }
-
-        let app_config = AppConfig::default();
-        if let Err(e) = std::fs::File::create(path).and_then(|mut file| {
-            let toml = toml::to_string(&app_config).unwrap();
-
-            file.write_all(toml.as_bytes())
-        }) {
-            print_err(e.to_string());
-            Err(())
-        } else {
-            if let Some(path_str) = path.to_str() {
-                println!("Config file created at {path_str}");
+            Ok(app_config) => {
+                if let Some(path_str) = path.to_str() {
+                    println!("Config file created at {path_str}");
+                }
+                Ok(app_config)
             }
-            Ok(app_config)
         }
     } else {
         Err(())
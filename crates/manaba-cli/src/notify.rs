@@ -0,0 +1,375 @@
+pub mod desktop;
+pub mod webhook;
+
+use crate::{
+    app_config::NotifyConfig,
+    error::{Result, print_err},
+    output::AssignmentRecord,
+    urgency,
+};
+use chrono::{DateTime, FixedOffset};
+use manaba_sdk::{
+    assignment::AssignmentSubmitState,
+    query::{AssignmentState, Queryable as _},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, path::PathBuf};
+
+pub enum AssignmentEvent {
+    New(AssignmentRecord),
+    BecameUrgent(AssignmentRecord),
+    DeadlineApproaching {
+        assignment: AssignmentRecord,
+        hours: u64,
+    },
+}
+
+impl AssignmentEvent {
+    pub fn assignment(&self) -> &AssignmentRecord {
+        match self {
+            AssignmentEvent::New(assignment)
+            | AssignmentEvent::BecameUrgent(assignment)
+            | AssignmentEvent::DeadlineApproaching { assignment, .. } => assignment,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AssignmentEvent::New(_) => "new",
+            AssignmentEvent::BecameUrgent(_) => "urgent",
+            AssignmentEvent::DeadlineApproaching { .. } => "approaching",
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        match self {
+            AssignmentEvent::New(_) => "New assignment".to_owned(),
+            AssignmentEvent::BecameUrgent(_) => "Deadline is approaching".to_owned(),
+            AssignmentEvent::DeadlineApproaching { hours, .. } => {
+                format!("Due within {hours} hours")
+            }
+        }
+    }
+
+    pub fn body(&self) -> String {
+        let assignment = self.assignment();
+        let due_date = assignment
+            .due_date
+            .map_or(String::new(), |d| d.format("%Y-%m-%d %H:%M").to_string());
+
+        format!(
+            "{}\n{}\n締切: {due_date}",
+            assignment.title, assignment.course_title
+        )
+    }
+
+    pub fn is_urgent(&self) -> bool {
+        !matches!(self, AssignmentEvent::New(_))
+    }
+}
+
+/// Deliver events to the desktop and webhook notifiers enabled in the config
+pub struct Notifier<'a> {
+    config: &'a NotifyConfig,
+    base_url: &'a str,
+    http: reqwest::Client,
+    dry_run: bool,
+}
+
+impl<'a> Notifier<'a> {
+    pub fn new(config: &'a NotifyConfig, base_url: &'a str, dry_run: bool) -> Self {
+        Self {
+            config,
+            base_url,
+            http: reqwest::Client::new(),
+            dry_run,
+        }
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Send an event to every notifier, reporting failures without aborting the others
+    pub async fn notify(&self, event: &AssignmentEvent) {
+        if self.config.desktop {
+            if self.dry_run {
+                println!("[desktop] {}\n{}\n", event.summary(), event.body());
+            } else if let Err(e) = desktop::send(event).await {
+                print_err(format!("Failed to send notification: {e}"));
+            }
+        }
+
+        if let Some(webhook_config) = &self.config.webhook {
+            let payload = webhook::payload(webhook_config.format, event, self.base_url);
+
+            if self.dry_run {
+                println!("[webhook] POST {}", webhook_config.url);
+                println!(
+                    "{}\n",
+                    serde_json::to_string_pretty(&payload).unwrap_or_default()
+                );
+            } else if let Err(e) = webhook::send(&self.http, webhook_config, &payload).await {
+                print_err(format!("Failed to send webhook: {e}"));
+            }
+        }
+    }
+}
+
+/// Alerts that have already fired, so that each alert fires only once
+#[derive(Serialize, Deserialize, Default)]
+pub struct NotifyState {
+    initialized: bool,
+    known: BTreeSet<String>,
+    urgent: BTreeSet<String>,
+    approaching: BTreeSet<String>,
+}
+
+impl NotifyState {
+    pub fn path() -> PathBuf {
+        let data_dir_path = dirs::data_dir().unwrap();
+        data_dir_path.join("manaba").join("notify_state.json")
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path();
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Compare assignments with the alerts already fired and return the new ones.
+    ///
+    /// The first run only records existing assignments instead of reporting all of them as new.
+    /// At most one event is returned per assignment, preferring the most urgent one.
+    pub fn detect(
+        &mut self,
+        assignments: &[AssignmentRecord],
+        lead_hours: &[u64],
+        now: DateTime<FixedOffset>,
+    ) -> Vec<AssignmentEvent> {
+        let mut events = Vec::new();
+
+        for assignment in assignments {
+            let is_new = self.known.insert(assignment.id.clone()) && self.initialized;
+
+            let is_open = assignment.state() == AssignmentState::Todo;
+
+            if !is_open {
+                if is_new && assignment.submit_state == AssignmentSubmitState::Todo {
+                    events.push(AssignmentEvent::New(assignment.clone()));
+                }
+                continue;
+            }
+
+            // Alerts are keyed by the deadline too, so that they fire again when it is moved
+            let deadline_key = match assignment.due_date {
+                Some(due_date) => format!("{}@{}", assignment.id, due_date.timestamp()),
+                None => assignment.id.clone(),
+            };
+
+            let mut approaching_hours = None;
+            if let Some(due_date) = assignment.due_date {
+                let remaining = due_date - now;
+
+                for &hours in lead_hours {
+                    let key = format!("{deadline_key}:{hours}h");
+                    if remaining > chrono::Duration::zero()
+                        && remaining <= chrono::Duration::hours(hours as i64)
+                        && self.approaching.insert(key)
+                    {
+                        approaching_hours =
+                            Some(approaching_hours.map_or(hours, |h: u64| h.min(hours)));
+                    }
+                }
+            }
+
+            let became_urgent = assignment.importance.is_some_and(urgency::is_urgent)
+                && self.urgent.insert(deadline_key);
+
+            if let Some(hours) = approaching_hours {
+                events.push(AssignmentEvent::DeadlineApproaching {
+                    assignment: assignment.clone(),
+                    hours,
+                });
+            } else if became_urgent {
+                events.push(AssignmentEvent::BecameUrgent(assignment.clone()));
+            } else if is_new {
+                events.push(AssignmentEvent::New(assignment.clone()));
+            }
+        }
+
+        self.initialized = true;
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone as _, Utc};
+    use manaba_sdk::{
+        assignment::{AssignmentImportanceLevel, AssignmentKind, AssignmentReceptibleState},
+        clock::{Clock as _, FixedClock},
+    };
+
+    const LEAD_HOURS: [u64; 2] = [24, 3];
+
+    fn now() -> DateTime<FixedOffset> {
+        FixedClock(Utc.with_ymd_and_hms(2026, 10, 20, 0, 0, 0).unwrap())
+            .now()
+            .fixed_offset()
+    }
+
+    fn assignment(
+        id: &str,
+        hours_left: i64,
+        importance: AssignmentImportanceLevel,
+    ) -> AssignmentRecord {
+        AssignmentRecord {
+            course_id: "course_11111".to_owned(),
+            course_title: "11111:Operating Systems".to_owned(),
+            kind: AssignmentKind::Report,
+            id: id.to_owned(),
+            title: "Essay".to_owned(),
+            submit_state: AssignmentSubmitState::Todo,
+            receptible_state: AssignmentReceptibleState::Open,
+            start_date: None,
+            due_date: Some(now() + chrono::Duration::hours(hours_left)),
+            importance: Some(importance),
+            local_status: None,
+            note: None,
+        }
+    }
+
+    fn kinds(events: &[AssignmentEvent]) -> Vec<(&str, &str)> {
+        events
+            .iter()
+            .map(|event| (event.assignment().id.as_str(), event.kind()))
+            .collect()
+    }
+
+    #[test]
+    fn first_sight_test() {
+        let mut state = NotifyState::default();
+        let far = assignment("report_1", 24 * 10, AssignmentImportanceLevel::Low);
+        let soon = assignment("report_2", 20, AssignmentImportanceLevel::High);
+
+        // Existing assignments are not new on the first run, but deadlines still alert
+        let events = state.detect(&[far.clone(), soon.clone()], &LEAD_HOURS, now());
+        assert_eq!(kinds(&events), [("report_2", "approaching")]);
+
+        let added = assignment("report_3", 24 * 10, AssignmentImportanceLevel::Low);
+        let events = state.detect(&[far, soon, added], &LEAD_HOURS, now());
+        assert_eq!(kinds(&events), [("report_3", "new")]);
+    }
+
+    #[test]
+    fn no_change_test() {
+        let mut state = NotifyState::default();
+        let assignments = [
+            assignment("report_1", 2, AssignmentImportanceLevel::High),
+            assignment("report_2", 24 * 5, AssignmentImportanceLevel::Medium),
+        ];
+
+        assert_eq!(state.detect(&assignments, &LEAD_HOURS, now()).len(), 1);
+        assert!(state.detect(&assignments, &LEAD_HOURS, now()).is_empty());
+
+        // Both lead times were reached at once, so they do not fire again later
+        let later = now() + chrono::Duration::hours(1);
+        assert!(state.detect(&assignments, &LEAD_HOURS, later).is_empty());
+    }
+
+    #[test]
+    fn lead_time_test() {
+        let mut state = NotifyState::default();
+        let assignments = [assignment(
+            "report_1",
+            30,
+            AssignmentImportanceLevel::Medium,
+        )];
+
+        assert!(state.detect(&assignments, &LEAD_HOURS, now()).is_empty());
+
+        let events = state.detect(
+            &assignments,
+            &LEAD_HOURS,
+            now() + chrono::Duration::hours(8),
+        );
+        assert!(matches!(
+            events[..],
+            [AssignmentEvent::DeadlineApproaching { hours: 24, .. }]
+        ));
+
+        let events = state.detect(
+            &assignments,
+            &LEAD_HOURS,
+            now() + chrono::Duration::hours(28),
+        );
+        assert!(matches!(
+            events[..],
+            [AssignmentEvent::DeadlineApproaching { hours: 3, .. }]
+        ));
+
+        // Past the deadline nothing fires any more
+        let events = state.detect(
+            &assignments,
+            &LEAD_HOURS,
+            now() + chrono::Duration::hours(31),
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn deadline_moved_test() {
+        let mut state = NotifyState::default();
+        let assignment_before = assignment("report_1", 20, AssignmentImportanceLevel::High);
+        assert_eq!(
+            kinds(&state.detect(&[assignment_before], &LEAD_HOURS, now())),
+            [("report_1", "approaching")]
+        );
+
+        // Extended by two days, then approaching again
+        let extended = assignment("report_1", 68, AssignmentImportanceLevel::Medium);
+        assert!(
+            state
+                .detect(std::slice::from_ref(&extended), &LEAD_HOURS, now())
+                .is_empty()
+        );
+
+        let two_days_later = now() + chrono::Duration::hours(48);
+        assert_eq!(
+            kinds(&state.detect(&[extended], &LEAD_HOURS, two_days_later)),
+            [("report_1", "approaching")]
+        );
+    }
+
+    #[test]
+    fn submitted_test() {
+        let mut state = NotifyState::default();
+        state.detect(&[], &LEAD_HOURS, now());
+
+        let mut submitted = assignment("report_1", 2, AssignmentImportanceLevel::High);
+        submitted.submit_state = AssignmentSubmitState::Done;
+        assert!(state.detect(&[submitted], &LEAD_HOURS, now()).is_empty());
+
+        // A closed assignment that was never submitted is still reported as new
+        let mut missed = assignment("report_2", -2, AssignmentImportanceLevel::Overdue);
+        missed.receptible_state = AssignmentReceptibleState::Closed;
+        assert_eq!(
+            kinds(&state.detect(&[missed], &LEAD_HOURS, now())),
+            [("report_2", "new")]
+        );
+    }
+}
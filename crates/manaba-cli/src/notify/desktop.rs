@@ -0,0 +1,21 @@
+use crate::{error::Result, notify::AssignmentEvent};
+use notify_rust::Notification;
+
+/// Send a notification through the freedesktop notification service on the session bus
+pub async fn send(event: &AssignmentEvent) -> Result<()> {
+    let mut notification = Notification::new();
+    notification
+        .appname("manaba")
+        .summary(&event.summary())
+        .body(&event.body());
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    notification.urgency(if event.is_urgent() {
+        notify_rust::Urgency::Critical
+    } else {
+        notify_rust::Urgency::Normal
+    });
+
+    tokio::task::spawn_blocking(move || notification.show()).await??;
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use crate::{
+    app_config::{WebhookConfig, WebhookFormat},
+    error::{Error, Result},
+    notify::AssignmentEvent,
+};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+#[cfg(not(test))]
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+#[cfg(test)]
+const INITIAL_BACKOFF: Duration = Duration::from_millis(10);
+
+pub fn payload(format: WebhookFormat, event: &AssignmentEvent, base_url: &str) -> Value {
+    let assignment = event.assignment();
+    let url = assignment.url(base_url);
+
+    match format {
+        WebhookFormat::Json => json!({
+            "event": event.kind(),
+            "hours": match event {
+                AssignmentEvent::DeadlineApproaching { hours, .. } => Some(*hours),
+                _ => None,
+            },
+            "url": url,
+            "assignment": assignment,
+        }),
+        WebhookFormat::Slack => json!({
+            "text": format!("*{}*\n<{url}|{}>\n{}", event.summary(), assignment.title, event.body()),
+        }),
+        WebhookFormat::Discord => json!({
+            "embeds": [{
+                "title": event.summary(),
+                "description": event.body(),
+                "url": url,
+            }],
+        }),
+    }
+}
+
+/// POST the payload, retrying with exponential backoff on network errors, 429 and 5xx
+pub async fn send(http: &reqwest::Client, config: &WebhookConfig, payload: &Value) -> Result<()> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 0;
+
+    loop {
+        let result = http.post(&config.url).json(payload).send().await;
+
+        let error = match result {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                let error = Error::Webhook(format!("{} responded with {status}", config.url));
+
+                if !(status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS) {
+                    return Err(error);
+                }
+                error
+            }
+            Err(e) => Error::Webhook(e.to_string()),
+        };
+
+        if attempt >= config.retries {
+            return Err(error);
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt as _, AsyncWriteExt as _},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+
+    /// Answer one request per status, or drop the connection for `None`, and return the request count
+    async fn server(statuses: Vec<Option<u16>>) -> (String, JoinHandle<usize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let mut requests = 0;
+            for status in statuses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                requests += 1;
+
+                let mut request = Vec::new();
+                let mut buf = [0; 4096];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+
+                if let Some(status) = status {
+                    let response = format!(
+                        "HTTP/1.1 {status} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                    );
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                }
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+
+    fn config(url: String, retries: u32) -> WebhookConfig {
+        WebhookConfig {
+            url,
+            format: WebhookFormat::Json,
+            retries,
+        }
+    }
+
+    async fn send_to(url: String, retries: u32) -> Result<()> {
+        send(&reqwest::Client::new(), &config(url, retries), &json!({})).await
+    }
+
+    #[tokio::test]
+    async fn retry_test() {
+        let (url, handle) = server(vec![Some(503), Some(429), None, Some(200)]).await;
+
+        assert!(send_to(url, 3).await.is_ok());
+        assert_eq!(handle.await.unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn retries_exhausted_test() {
+        let (url, handle) = server(vec![Some(500), Some(502), Some(500)]).await;
+
+        let result = send_to(url, 2).await;
+        assert!(
+            matches!(result, Err(Error::Webhook(message)) if message.ends_with("500 Internal Server Error"))
+        );
+        assert_eq!(handle.await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn client_error_test() {
+        let (url, handle) = server(vec![Some(404)]).await;
+
+        assert!(matches!(send_to(url, 3).await, Err(Error::Webhook(_))));
+        assert_eq!(handle.await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn network_error_test() {
+        // Nothing listens on the port once the listener is dropped
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+
+        assert!(matches!(send_to(url, 1).await, Err(Error::Webhook(_))));
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{error::Result, overlay, timezone, urgency};
+use chrono::{DateTime, FixedOffset, Utc, Weekday};
+use clap::ValueEnum;
+use manaba_sdk::{
+    Course, Exam, Report, Term, TimetableEntry,
+    assignment::{
+        AssignmentImportanceLevel, AssignmentKind, AssignmentReceptibleState, AssignmentSubmitState,
+    },
+    overlay::{LocalStatus, apply_status},
+    query::{AssignmentState, Queryable},
+    todo::Todo,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::io::Write as _;
+
+const ISO_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%:z";
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Colored text for humans
+    #[default]
+    Text,
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    Csv,
+    Tsv,
+    Yaml,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AssignmentRecord {
+    pub course_id: String,
+    pub course_title: String,
+    pub kind: AssignmentKind,
+    pub id: String,
+    pub title: String,
+    pub submit_state: AssignmentSubmitState,
+    pub receptible_state: AssignmentReceptibleState,
+    #[serde(
+        serialize_with = "serialize_iso_date",
+        deserialize_with = "deserialize_iso_date"
+    )]
+    pub start_date: Option<DateTime<FixedOffset>>,
+    #[serde(
+        serialize_with = "serialize_iso_date",
+        deserialize_with = "deserialize_iso_date"
+    )]
+    pub due_date: Option<DateTime<FixedOffset>>,
+    pub importance: Option<AssignmentImportanceLevel>,
+    /// status from `manaba mark`
+    #[serde(default)]
+    pub local_status: Option<LocalStatus>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TimetableRecord {
+    pub weekday: Weekday,
+    pub period: u32,
+    /// period time from `[timetable]` in settings.toml
+    pub time: Option<String>,
+    pub course_id: String,
+    pub course_title: String,
+    pub room: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CourseRecord {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub year: Option<i32>,
+    pub term: Option<Term>,
+    pub schedule: Option<String>,
+    /// comma separated, so the record stays flat for CSV
+    pub instructors: String,
+    pub status: Option<String>,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+}
+
+impl AssignmentRecord {
+    pub fn from_report(course: &Course, report: &Report) -> Self {
+        Self {
+            course_id: course.id.clone(),
+            course_title: course.title.clone(),
+            kind: AssignmentKind::Report,
+            id: report.id.clone(),
+            title: report.title.clone(),
+            submit_state: report.submit_state,
+            receptible_state: report.receptiable_state,
+            start_date: report
+                .start_date
+                .as_ref()
+                .map(|d| timezone::to_display(&d.date)),
+            due_date: report
+                .due_date
+                .as_ref()
+                .map(|d| timezone::to_display(&d.date)),
+            importance: report
+                .due_date
+                .as_ref()
+                .map(|d| urgency::importance_level(&d.date)),
+            local_status: overlay::local_state(&report.id).and_then(|local| local.status),
+            note: overlay::local_state(&report.id).and_then(|local| local.note.clone()),
+        }
+    }
+
+    pub fn from_exam(course: &Course, exam: &Exam) -> Self {
+        Self {
+            course_id: course.id.clone(),
+            course_title: course.title.clone(),
+            kind: AssignmentKind::Exam,
+            id: exam.id.clone(),
+            title: exam.title.clone(),
+            submit_state: exam.submit_state,
+            receptible_state: exam.receptiable_state,
+            start_date: exam
+                .start_date
+                .as_ref()
+                .map(|d| timezone::to_display(&d.date)),
+            due_date: exam
+                .due_date
+                .as_ref()
+                .map(|d| timezone::to_display(&d.date)),
+            importance: exam
+                .due_date
+                .as_ref()
+                .map(|d| urgency::importance_level(&d.date)),
+            local_status: overlay::local_state(&exam.id).and_then(|local| local.status),
+            note: overlay::local_state(&exam.id).and_then(|local| local.note.clone()),
+        }
+    }
+
+    pub fn from_todo(todo: &Todo) -> Self {
+        Self {
+            course_id: todo.course_id.clone().unwrap_or_default(),
+            course_title: todo.course_title.clone().unwrap_or_default(),
+            kind: AssignmentKind::Todo,
+            id: todo.id.clone(),
+            title: todo.title.clone(),
+            submit_state: if todo.done {
+                AssignmentSubmitState::Done
+            } else {
+                AssignmentSubmitState::Todo
+            },
+            receptible_state: AssignmentReceptibleState::Open,
+            start_date: None,
+            due_date: todo.due_date.as_ref().map(timezone::to_display),
+            importance: todo.due_date.as_ref().map(urgency::importance_level),
+            local_status: overlay::local_state(&todo.id).and_then(|local| local.status),
+            note: overlay::local_state(&todo.id).and_then(|local| local.note.clone()),
+        }
+    }
+
+    /// Page of the assignment, or of its course for todos which have none
+    pub fn url(&self, base_url: &str) -> String {
+        match self.kind {
+            AssignmentKind::Todo if self.course_id.is_empty() => base_url.to_owned(),
+            AssignmentKind::Todo => format!("{}/{}", base_url, self.course_id),
+            _ => format!("{}/{}", base_url, self.id),
+        }
+    }
+}
+
+impl Queryable for AssignmentRecord {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn kind(&self) -> AssignmentKind {
+        self.kind
+    }
+
+    fn course_id(&self) -> &str {
+        &self.course_id
+    }
+
+    fn course_title(&self) -> &str {
+        &self.course_title
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn state(&self) -> AssignmentState {
+        apply_status(
+            AssignmentState::new(self.submit_state, self.receptible_state),
+            self.local_status,
+        )
+    }
+
+    fn due_date(&self) -> Option<DateTime<Utc>> {
+        self.due_date.map(|d| d.to_utc())
+    }
+}
+
+impl TimetableRecord {
+    pub fn from_entry(entry: &TimetableEntry, time: Option<&String>) -> Self {
+        Self {
+            weekday: entry.weekday,
+            period: entry.period,
+            time: time.cloned(),
+            course_id: entry.course.id.clone(),
+            course_title: entry.course.title.clone(),
+            room: entry.room.clone(),
+        }
+    }
+}
+
+fn serialize_iso_date<S: Serializer>(
+    date: &Option<DateTime<FixedOffset>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match date {
+        Some(date) => serializer.serialize_str(&date.format(ISO_DATE_FORMAT).to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_iso_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<DateTime<FixedOffset>>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|date| DateTime::parse_from_str(&date, ISO_DATE_FORMAT))
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+pub fn print_records<T: Serialize>(records: &[T], format: OutputFormat) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+
+    match format {
+        OutputFormat::Text => unreachable!("text output is rendered by each command"),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut stdout, records)?;
+            writeln!(stdout)?;
+        }
+        OutputFormat::Ndjson => {
+            for record in records {
+                serde_json::to_writer(&mut stdout, record)?;
+                writeln!(stdout)?;
+            }
+        }
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let delimiter = if format == OutputFormat::Csv {
+                b','
+            } else {
+                b'\t'
+            };
+            let mut writer = csv::WriterBuilder::new()
+                .delimiter(delimiter)
+                .from_writer(stdout);
+
+            for record in records {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+        }
+        OutputFormat::Yaml => {
+            serde_yaml::to_writer(&mut stdout, records)?;
+        }
+    }
+
+    Ok(())
+}
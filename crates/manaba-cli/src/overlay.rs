@@ -0,0 +1,28 @@
+use crate::error::{Result, print_err};
+use manaba_sdk::overlay::{LocalState, Overlay};
+use std::{path::PathBuf, sync::OnceLock};
+
+/// Local states read once per run, see `manaba mark`
+static APP_OVERLAY: OnceLock<Overlay> = OnceLock::new();
+
+pub fn overlay_path() -> PathBuf {
+    Overlay::default_path().unwrap()
+}
+
+pub fn overlay() -> &'static Overlay {
+    APP_OVERLAY.get_or_init(|| {
+        load().unwrap_or_else(|e| {
+            print_err(format!("Failed to load {}: {e}", overlay_path().display()));
+            Overlay::default()
+        })
+    })
+}
+
+pub fn local_state(id: &str) -> Option<&'static LocalState> {
+    overlay().get(id)
+}
+
+/// Load the overlay for editing
+pub fn load() -> Result<Overlay> {
+    Ok(Overlay::load(&overlay_path())?)
+}
@@ -0,0 +1,111 @@
+use crate::{
+    APP_CONFIG,
+    error::{Error, Result},
+    is_interactive,
+};
+use dialoguer::{FuzzySelect, theme::ColorfulTheme};
+use std::{
+    io::Write as _,
+    process::{Command, Stdio},
+};
+
+/// Show the label and preview the third field of each line
+const FINDER_ARGS: [&str; 7] = [
+    "--ansi",
+    "--delimiter",
+    "\t",
+    "--with-nth",
+    "2",
+    "--preview",
+    "printf '%b\\n' {3}",
+];
+
+/// Lines of a detail page shown below the summary of an item
+const PAGE_PREVIEW_LINES: usize = 40;
+
+pub struct PickItem {
+    pub label: String,
+    /// Details shown next to the list by the external finder
+    pub preview: String,
+}
+
+/// Append the text of the detail page of an item to its preview
+pub fn push_page(lines: &mut Vec<String>, page: Option<&[String]>) {
+    if let Some(page) = page.filter(|page| !page.is_empty()) {
+        lines.push(String::new());
+        lines.extend(page.iter().take(PAGE_PREVIEW_LINES).cloned());
+    }
+}
+
+/// Let the user pick one of `items` with `finder` from the config file or the built-in fuzzy finder
+///
+/// Returns `None` when the selection is cancelled.
+pub fn pick(prompt: &str, items: &[PickItem]) -> Result<Option<usize>> {
+    if !is_interactive() {
+        return Err(Error::NonInteractive("pick from a list"));
+    }
+
+    let app_config = APP_CONFIG.get().unwrap();
+
+    match app_config.finder.as_deref().map(str::trim) {
+        Some(command) if !command.is_empty() => pick_external(command, items),
+        _ => {
+            let labels = items.iter().map(|item| &item.label).collect::<Vec<_>>();
+
+            Ok(FuzzySelect::with_theme(&ColorfulTheme::default())
+                .with_prompt(prompt)
+                .default(0)
+                .max_length(15)
+                .items(&labels)
+                .interact_opt()?)
+        }
+    }
+}
+
+/// Pipe `index<TAB>label<TAB>preview` lines to an fzf compatible finder and read back the index
+fn pick_external(command: &str, items: &[PickItem]) -> Result<Option<usize>> {
+    let finder_err = |source| Error::Finder {
+        command: command.to_owned(),
+        source,
+    };
+
+    let mut words = command.split_whitespace();
+    let mut child = Command::new(words.next().unwrap())
+        .args(words)
+        .args(FINDER_ARGS)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(finder_err)?;
+
+    let mut input = String::new();
+    for (index, item) in items.iter().enumerate() {
+        input.push_str(&format!(
+            "{index}\t{}\t{}\n",
+            item.label.replace(['\t', '\n'], " "),
+            escape_preview(&item.preview)
+        ));
+    }
+
+    // The finder exits early when the user cancels before reading everything
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    let output = child.wait_with_output().map_err(finder_err)?;
+
+    // fzf exits with 1 when nothing matched and 130 when cancelled
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\t')
+        .next()
+        .and_then(|index| index.trim().parse().ok()))
+}
+
+/// Keep the preview on one line for `printf '%b'`
+fn escape_preview(preview: &str) -> String {
+    preview
+        .replace('\\', "\\\\")
+        .replace('\t', " ")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,260 @@
+use crate::{
+    error::{Error, Result},
+    output::AssignmentRecord,
+};
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use std::fmt::Write as _;
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// A one-line output template such as `{course} | {title} | {due:%m/%d %H:%M}`.
+///
+/// Placeholders are written as `{field}` or `{field:format}`, where `format`
+/// is a `strftime` format for date fields. `{{` and `}}` print literal braces.
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+enum Segment {
+    Literal(String),
+    Field {
+        field: Field,
+        format: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    Course,
+    CourseId,
+    Kind,
+    Id,
+    Title,
+    State,
+    Receptible,
+    Start,
+    Due,
+    Importance,
+    Local,
+    Note,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        let field = match name {
+            "course" => Field::Course,
+            "course_id" => Field::CourseId,
+            "kind" => Field::Kind,
+            "id" => Field::Id,
+            "title" => Field::Title,
+            "state" => Field::State,
+            "receptible" => Field::Receptible,
+            "start" => Field::Start,
+            "due" => Field::Due,
+            "importance" => Field::Importance,
+            "local" => Field::Local,
+            "note" => Field::Note,
+            _ => return None,
+        };
+
+        Some(field)
+    }
+
+    fn is_date(self) -> bool {
+        matches!(self, Field::Start | Field::Due)
+    }
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => {
+                                return Err(Error::Template(format!(
+                                    "unclosed placeholder `{{{placeholder}`"
+                                )));
+                            }
+                        }
+                    }
+
+                    let (name, format) = match placeholder.split_once(':') {
+                        Some((name, format)) => (name.trim(), Some(format.to_owned())),
+                        None => (placeholder.trim(), None),
+                    };
+
+                    let field = Field::parse(name)
+                        .ok_or_else(|| Error::Template(format!("unknown field `{name}`")))?;
+
+                    if format.is_some() && !field.is_date() {
+                        return Err(Error::Template(format!(
+                            "field `{name}` does not take a format"
+                        )));
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field { field, format });
+                }
+                '}' => {
+                    return Err(Error::Template(
+                        "unmatched `}`, use `}}` for a literal brace".to_owned(),
+                    ));
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Template { segments })
+    }
+
+    pub fn render(&self, record: &AssignmentRecord) -> String {
+        let mut s = String::new();
+
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => s.push_str(literal),
+                Segment::Field { field, format } => {
+                    let format = format.as_deref().unwrap_or(DEFAULT_DATE_FORMAT);
+
+                    match field {
+                        Field::Course => s.push_str(&record.course_title),
+                        Field::CourseId => s.push_str(&record.course_id),
+                        Field::Kind => s.push_str(&to_snake_case(&record.kind)),
+                        Field::Id => s.push_str(&record.id),
+                        Field::Title => s.push_str(&record.title),
+                        Field::State => s.push_str(&to_snake_case(&record.submit_state)),
+                        Field::Receptible => s.push_str(&to_snake_case(&record.receptible_state)),
+                        Field::Start => write_date(&mut s, record.start_date, format),
+                        Field::Due => write_date(&mut s, record.due_date, format),
+                        Field::Importance => {
+                            if let Some(importance) = &record.importance {
+                                s.push_str(&to_snake_case(importance));
+                            }
+                        }
+                        Field::Local => {
+                            if let Some(local_status) = &record.local_status {
+                                s.push_str(&to_snake_case(local_status));
+                            }
+                        }
+                        Field::Note => s.push_str(record.note.as_deref().unwrap_or_default()),
+                    }
+                }
+            }
+        }
+
+        s
+    }
+}
+
+fn write_date(s: &mut String, date: Option<DateTime<FixedOffset>>, format: &str) {
+    if let Some(date) = date {
+        // An invalid format is rendered as-is rather than panicking.
+        let mut formatted = String::new();
+        if write!(formatted, "{}", date.format(format)).is_ok() {
+            s.push_str(&formatted);
+        } else {
+            s.push_str(format);
+        }
+    }
+}
+
+/// Render a unit enum variant the same way it is serialized in JSON output
+fn to_snake_case<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(ToOwned::to_owned))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use manaba_sdk::assignment::{
+        AssignmentImportanceLevel, AssignmentKind, AssignmentReceptibleState, AssignmentSubmitState,
+    };
+
+    fn record() -> AssignmentRecord {
+        let due_date = DateTime::parse_from_rfc3339("2026-10-20T23:59:00+09:00").unwrap();
+
+        AssignmentRecord {
+            course_id: "course_11111".to_owned(),
+            course_title: "11111:Operating Systems".to_owned(),
+            kind: AssignmentKind::Report,
+            id: "course_11111_report_1".to_owned(),
+            title: "Essay".to_owned(),
+            submit_state: AssignmentSubmitState::Todo,
+            receptible_state: AssignmentReceptibleState::Open,
+            start_date: None,
+            due_date: Some(due_date),
+            importance: Some(AssignmentImportanceLevel::High),
+            local_status: None,
+            note: None,
+        }
+    }
+
+    fn render(template: &str) -> String {
+        Template::parse(template).unwrap().render(&record())
+    }
+
+    fn parse_error(template: &str) -> String {
+        match Template::parse(template) {
+            Ok(_) => panic!("`{template}` should not parse"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn fields_test() {
+        assert_eq!(
+            render("{course} | {title} ({kind}, {state}, {importance})"),
+            "11111:Operating Systems | Essay (report, todo, high)"
+        );
+        // Missing values render as nothing
+        assert_eq!(render("[{start}][{note}][{local}]"), "[][][]");
+    }
+
+    #[test]
+    fn date_format_test() {
+        assert_eq!(render("{due}"), "2026-10-20 23:59");
+        assert_eq!(render("{due:%m/%d %H:%M}"), "10/20 23:59");
+        assert_eq!(render("{ due :%Y}"), "2026");
+        assert!(parse_error("{title:%Y}").contains("does not take a format"));
+    }
+
+    #[test]
+    fn escaped_braces_test() {
+        assert_eq!(render("{{{title}}}"), "{Essay}");
+        assert_eq!(render("{{title}}"), "{title}");
+    }
+
+    #[test]
+    fn invalid_template_test() {
+        assert!(parse_error("{deadline}").contains("unknown field `deadline`"));
+        assert!(parse_error("{title").contains("unclosed placeholder"));
+        assert!(parse_error("title}").contains("unmatched `}`"));
+    }
+}
@@ -0,0 +1,55 @@
+use crate::timezone;
+use manaba_sdk::{AcademicTerm, Course, Term};
+use std::sync::OnceLock;
+
+/// Courses to include, set by `--term`
+pub static APP_TERM: OnceLock<TermFilter> = OnceLock::new();
+
+#[derive(Clone, Copy)]
+pub enum TermFilter {
+    All,
+    /// The term of today, or of `--as-of`
+    Current,
+    Year(i32),
+    Term(AcademicTerm),
+}
+
+impl TermFilter {
+    pub fn matches(&self, course: &Course) -> bool {
+        match self {
+            TermFilter::All => true,
+            TermFilter::Current => {
+                let today = timezone::institution_now().date_naive();
+                course.is_held_in(&AcademicTerm::at(today))
+            }
+            TermFilter::Year(year) => course.year.is_none_or(|course_year| course_year == *year),
+            TermFilter::Term(academic_term) => course.is_held_in(academic_term),
+        }
+    }
+}
+
+/// Parse `current`, `all`, a year such as `2026`, or a term such as `2026-first`
+pub fn parse_term_filter(s: &str) -> Result<TermFilter, String> {
+    let invalid = || format!("invalid term `{s}`, expected current, all, 2026 or 2026-first");
+
+    match s {
+        "all" => return Ok(TermFilter::All),
+        "current" => return Ok(TermFilter::Current),
+        _ => {}
+    }
+
+    let (year, term) = match s.split_once('-') {
+        Some((year, term)) => (year, Some(term)),
+        None => (s, None),
+    };
+    let year = year.parse().map_err(|_| invalid())?;
+
+    let term = match term {
+        None => return Ok(TermFilter::Year(year)),
+        Some("first") => Term::First,
+        Some("second") => Term::Second,
+        Some(_) => return Err(invalid()),
+    };
+
+    Ok(TermFilter::Term(AcademicTerm { year, term }))
+}
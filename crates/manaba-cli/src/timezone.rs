@@ -0,0 +1,109 @@
+use crate::{
+    APP_CONFIG,
+    error::{Error, Result},
+};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use manaba_sdk::{
+    Tz,
+    clock::{Clock, SystemClock},
+};
+use std::sync::OnceLock;
+
+/// Clock used for urgency, stopped at `--as-of` when it is given
+pub static APP_CLOCK: OnceLock<Box<dyn Clock + Send + Sync>> = OnceLock::new();
+
+/// Convert a date into `[timezone] display`, or the system time zone when it is unset
+pub fn to_display<Tz: TimeZone>(date: &DateTime<Tz>) -> DateTime<FixedOffset> {
+    let app_config = APP_CONFIG.get().unwrap();
+
+    match app_config.timezone.display {
+        Some(display) => date.with_timezone(&display).fixed_offset(),
+        None => date.with_timezone(&Local).fixed_offset(),
+    }
+}
+
+pub fn now() -> DateTime<FixedOffset> {
+    to_display(&clock().now())
+}
+
+/// Current time in `[timezone] institution`, which class periods are defined in
+pub fn institution_now() -> DateTime<Tz> {
+    let app_config = APP_CONFIG.get().unwrap();
+    clock()
+        .now()
+        .with_timezone(&app_config.timezone.institution)
+}
+
+fn clock() -> &'static (dyn Clock + Send + Sync) {
+    APP_CLOCK.get_or_init(|| Box::new(SystemClock)).as_ref()
+}
+
+/// A time given as an argument, which is parsed before the config is loaded
+#[derive(Clone, Copy)]
+pub enum TimeArg {
+    Exact(DateTime<Utc>),
+    /// Wall clock time in the display time zone
+    Local(NaiveDateTime),
+}
+
+impl TimeArg {
+    pub fn resolve(self) -> Result<DateTime<Utc>> {
+        match self {
+            TimeArg::Exact(date) => Ok(date),
+            TimeArg::Local(naive) => from_display_local(&naive)
+                .ok_or_else(|| Error::InvalidTime(naive.format("%Y-%m-%d %H:%M").to_string())),
+        }
+    }
+}
+
+/// Parse `2026-10-25T09:00` or `2026-10-25` in the display time zone, or an RFC 3339 date with an offset
+pub fn parse_date_time(s: &str) -> Result<TimeArg, String> {
+    const FORMATS: [&str; 4] = [
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%d %H:%M:%S",
+    ];
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(s) {
+        return Ok(TimeArg::Exact(date.with_timezone(&Utc)));
+    }
+
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_time(NaiveTime::MIN))
+        })
+        .map(TimeArg::Local)
+        .ok_or_else(|| format!("invalid date `{s}`, expected e.g. 2026-10-25T09:00"))
+}
+
+/// Like `parse_date_time`, but a date alone means the end of that day
+pub fn parse_deadline(s: &str) -> Result<TimeArg, String> {
+    match NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        Ok(date) => {
+            let end_of_day = NaiveTime::from_hms_opt(23, 59, 0).unwrap();
+            Ok(TimeArg::Local(date.and_time(end_of_day)))
+        }
+        Err(_) => parse_date_time(s),
+    }
+}
+
+/// A wall clock time in the display time zone as UTC
+fn from_display_local(naive: &NaiveDateTime) -> Option<DateTime<Utc>> {
+    let app_config = APP_CONFIG.get().unwrap();
+
+    match app_config.timezone.display {
+        Some(display) => display
+            .from_local_datetime(naive)
+            .earliest()
+            .map(|date| date.with_timezone(&Utc)),
+        None => Local
+            .from_local_datetime(naive)
+            .earliest()
+            .map(|date| date.with_timezone(&Utc)),
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{APP_CONFIG, output::AssignmentRecord, timezone};
+use chrono::{DateTime, TimeZone};
+use manaba_sdk::{
+    assignment::{AssignmentImportanceLevel, UrgencyPolicy},
+    query::{AssignmentState, Queryable as _},
+};
+
+/// Classify a deadline with the `[urgency]` policy from the config
+pub fn importance_level<Tz: TimeZone>(due_date: &DateTime<Tz>) -> AssignmentImportanceLevel {
+    let app_config = APP_CONFIG.get().unwrap();
+    app_config
+        .urgency
+        .importance_level(due_date, &timezone::now())
+}
+
+/// How far `Medium` reaches under the `[urgency]` policy, such as "7 days" or "120 hours"
+pub fn medium_reach() -> String {
+    let app_config = APP_CONFIG.get().unwrap();
+
+    match app_config.urgency.medium_hours {
+        Some(hours) => format!("{hours} hours"),
+        None => format!("{} days", UrgencyPolicy::MEDIUM_DAYS),
+    }
+}
+
+/// High, due today and overdue are all treated as needing attention now
+pub fn is_urgent(importance_level: AssignmentImportanceLevel) -> bool {
+    matches!(
+        importance_level,
+        AssignmentImportanceLevel::High
+            | AssignmentImportanceLevel::DueToday
+            | AssignmentImportanceLevel::Overdue
+    )
+}
+
+/// An open item due urgently, which makes `manaba check` exit with [`EXIT_URGENT`]
+///
+/// [`EXIT_URGENT`]: crate::error::EXIT_URGENT
+pub fn is_pending_urgent<Tz: TimeZone>(
+    state: AssignmentState,
+    due_date: Option<&DateTime<Tz>>,
+) -> bool {
+    state == AssignmentState::Todo
+        && due_date.is_some_and(|due_date| is_urgent(importance_level(due_date)))
+}
+
+pub fn count_urgent(records: &[AssignmentRecord]) -> usize {
+    records
+        .iter()
+        .filter(|record| is_pending_urgent(record.state(), record.due_date.as_ref()))
+        .count()
+}
@@ -0,0 +1,181 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CONFIG: &str = r#"base_url = "https://ct.ritsumei.ac.jp/ct/"
+cookie_domain = "ct.ritsumei.ac.jp"
+
+[timetable]
+
+[color]
+
+[timezone]
+display = "Asia/Tokyo"
+"#;
+
+/// A home directory of its own for each test, with only a config file in it
+fn home(name: &str) -> PathBuf {
+    let home = empty_home(name);
+
+    let config_dir = home.join(".config").join("manaba");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(config_dir.join("settings.toml"), CONFIG).unwrap();
+
+    home
+}
+
+fn empty_home(name: &str) -> PathBuf {
+    let home = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&home);
+    fs::create_dir_all(&home).unwrap();
+
+    home
+}
+
+/// Run `manaba` without access to the user's config, data or browser cookies
+fn manaba(home: &PathBuf, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_manaba"))
+        .arg("--non-interactive")
+        .args(args)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("XDG_DATA_HOME", home.join(".local").join("share"))
+        .env("XDG_CACHE_HOME", home.join(".cache"))
+        .output()
+        .unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// Dates are parsed in the display time zone, which needs the config to be loaded first
+#[test]
+fn as_of_test() {
+    let home = home("as_of_test");
+    let output = manaba(&home, &["--as-of", "2026-10-25T09:00", "config", "path"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+}
+
+#[test]
+fn due_bound_test() {
+    let home = home("due_bound_test");
+
+    for flag in ["--due-before", "--due-after"] {
+        let output = manaba(&home, &["check", flag, "2026-10-25"]);
+
+        // Parsing succeeds, then there is no cookie to log in with
+        assert_eq!(output.status.code(), Some(4), "{}", stderr(&output));
+    }
+}
+
+#[test]
+fn todo_due_test() {
+    let home = home("todo_due_test");
+    let output = manaba(&home, &["todo", "add", "x", "--due", "2026-10-20"]);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+}
+
+#[test]
+fn invalid_argument_test() {
+    let home = home("invalid_argument_test");
+    let output = manaba(&home, &["todo", "add", "x", "--due", "someday"]);
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+/// Commands that do not read the config never complain that it is missing
+#[test]
+fn without_config_test() {
+    let home = empty_home("without_config_test");
+
+    for args in [&["completions", "bash"][..], &["config", "path"]] {
+        let output = manaba(&home, args);
+
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert_eq!(stderr(&output), "");
+    }
+
+    let output = manaba(&home, &["config", "init"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stderr(&output), "");
+    assert!(home.join(".config/manaba/settings.toml").exists());
+}
+
+/// A cache holding one open report due 2026-10-20 23:59 in Tokyo, fetched just now
+fn cache_one_report(home: &Path) {
+    let fetched_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let cache = format!(
+        r#"{{"fetched_at": {fetched_at}, "courses": [], "assignments": [{{
+            "course_id": "course_1", "course_title": "1:Math", "kind": "report",
+            "id": "course_1_report_2", "title": "Essay", "submit_state": "todo",
+            "receptible_state": "open", "start_date": null,
+            "due_date": "2026-10-20T23:59:00+09:00", "importance": null
+        }}]}}"#
+    );
+
+    let cache_dir = home.join(".cache").join("manaba");
+    fs::create_dir_all(&cache_dir).unwrap();
+    fs::write(cache_dir.join("assignments.json"), cache).unwrap();
+}
+
+#[test]
+fn status_exit_code_test() {
+    let home = home("status_exit_code_test");
+    cache_one_report(&home);
+
+    let code = |as_of: &str, args: &[&str]| {
+        let args = [&["--as-of", as_of, "status"], args].concat();
+        manaba(&home, &args).status.code()
+    };
+
+    // Urgent
+    assert_eq!(code("2026-10-20T12:00", &[]), Some(3));
+    assert_eq!(code("2026-10-20T12:00", &["--json"]), Some(33));
+    assert_eq!(code("2026-10-20T12:00", &["--i3blocks"]), Some(33));
+
+    // Due within a week, which status bars do not treat as an error
+    assert_eq!(code("2026-10-15T12:00", &[]), Some(7));
+    assert_eq!(code("2026-10-15T12:00", &["--json"]), Some(0));
+    assert_eq!(code("2026-10-15T12:00", &["--i3blocks"]), Some(0));
+}
+
+#[test]
+fn status_i3blocks_test() {
+    let home = home("status_i3blocks_test");
+    cache_one_report(&home);
+
+    let output = manaba(
+        &home,
+        &["--as-of", "2026-10-20T12:00", "status", "--i3blocks"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        ["⚠1 ●0 ○0 | 10/20 23:59 Essay", "⚠1 ●0 ○0", "#E67E80"]
+    );
+}
+
+#[test]
+fn config_password_test() {
+    let home = home("config_password_test");
+    let smtp = "\n[smtp]\nhost = \"localhost\"\npassword = \"secret\"\nfrom = \"a@example.com\"\nto = []\n";
+    let settings = home.join(".config/manaba/settings.toml");
+    fs::write(&settings, format!("{CONFIG}{smtp}")).unwrap();
+
+    let output = manaba(&home, &["config", "get", "smtp.password"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "********\n");
+
+    for args in [&["config", "get", "smtp"][..], &["config", "show"]] {
+        let output = manaba(&home, args);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("********") && !stdout.contains("secret"));
+    }
+}
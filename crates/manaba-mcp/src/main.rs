@@ -1,15 +1,24 @@
 use anyhow::Result;
-use manaba_sdk::{Client, Cookie};
+use chrono::{DateTime, Utc};
+use manaba_sdk::{
+    Client, Cookie, Course, Exam, Report,
+    assignment::{AssignmentDate, AssignmentKind, UrgencyPolicy},
+    clock::SystemClock,
+    overlay::{LocalState, Overlay},
+    query::{AssignmentQuery, AssignmentState, Queryable},
+    todo::{Todo, TodoList},
+};
 use rmcp::{
     ServerHandler,
     model::{
         CallToolRequestMethod, CallToolRequestParam, CallToolResult, Content, Implementation,
-        InitializeResult, ListResourcesResult, ListToolsResult, PaginatedRequestParam, ServerCapabilities, Tool,
-        ToolsCapability,
+        InitializeResult, ListResourcesResult, ListToolsResult, PaginatedRequestParam,
+        ServerCapabilities, Tool, ToolsCapability,
     },
     service::{RequestContext, RoleServer, serve_server},
     transport::io::stdio,
 };
+use serde::Deserialize;
 use serde_json::json;
 use std::{borrow::Cow, sync::Arc};
 use tokio::sync::OnceCell;
@@ -28,9 +37,192 @@ async fn get_client() -> Result<&'static Client> {
         .await
 }
 
+/// Local states from `manaba mark`, read on every call so new marks show up
+fn load_overlay() -> Overlay {
+    let Some(path) = Overlay::default_path() else {
+        return Overlay::default();
+    };
+
+    Overlay::load(&path).unwrap_or_else(|e| {
+        eprintln!("Failed to load {}: {}", path.display(), e);
+        Overlay::default()
+    })
+}
+
+/// `[urgency]` from the CLI's settings.toml, read on every call like the overlay
+fn load_urgency_policy() -> UrgencyPolicy {
+    #[derive(Deserialize)]
+    struct Settings {
+        #[serde(default)]
+        urgency: UrgencyPolicy,
+    }
+
+    let Some(path) = dirs::config_dir().map(|dir| dir.join("manaba").join("settings.toml")) else {
+        return UrgencyPolicy::default();
+    };
+
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return UrgencyPolicy::default(),
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", path.display(), e);
+            return UrgencyPolicy::default();
+        }
+    };
+
+    let policy = match toml::from_str::<Settings>(&content) {
+        Ok(settings) => settings.urgency,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", path.display(), e);
+            return UrgencyPolicy::default();
+        }
+    };
+
+    match policy.validate() {
+        Ok(()) => policy,
+        Err(e) => {
+            eprintln!("Ignoring [urgency]: {}", e);
+            UrgencyPolicy::default()
+        }
+    }
+}
+
+fn importance_level(date: &AssignmentDate, policy: &UrgencyPolicy) -> String {
+    format!("{:?}", date.importance_level(policy, &SystemClock))
+}
+
 #[derive(Clone, Debug)]
 struct ManabaServer;
 
+/// Personal items from `manaba todo`, read on every call like the overlay
+fn load_todos() -> TodoList {
+    let Some(path) = TodoList::default_path() else {
+        return TodoList::default();
+    };
+
+    TodoList::load(&path).unwrap_or_else(|e| {
+        eprintln!("Failed to load {}: {}", path.display(), e);
+        TodoList::default()
+    })
+}
+
+/// A report, an exam or a todo, so all of them can be filtered and sorted together
+enum Assignment<'a> {
+    Report(&'a Course, &'a Report),
+    Exam(&'a Course, &'a Exam),
+    Todo(&'a Todo),
+}
+
+impl Assignment<'_> {
+    fn to_json(
+        &self,
+        local: Option<&LocalState>,
+        client: &Client,
+        policy: &UrgencyPolicy,
+    ) -> serde_json::Value {
+        match self {
+            Assignment::Report(course, report) => json!({
+                "type": "report",
+                "id": report.id,
+                "url": report.url(&client.base_url),
+                "course_id": course.id,
+                "course_title": course.title,
+                "title": report.title,
+                "submit_state": format!("{:?}", report.submit_state),
+                "receptible_state": format!("{:?}", report.receptiable_state),
+                "start_date": report.start_date.as_ref().map(|d| d.date.to_rfc3339()),
+                "due_date": report.due_date.as_ref().map(|d| d.date.to_rfc3339()),
+                "importance_level": report.due_date.as_ref().map(|d| importance_level(d, policy)),
+                "local_status": local.and_then(|local| local.status),
+                "note": local.and_then(|local| local.note.as_ref())
+            }),
+            Assignment::Exam(course, exam) => json!({
+                "type": "exam",
+                "id": exam.id,
+                "url": exam.url(&client.base_url),
+                "course_id": course.id,
+                "course_title": course.title,
+                "title": exam.title,
+                "submit_state": format!("{:?}", exam.submit_state),
+                "receptible_state": format!("{:?}", exam.receptiable_state),
+                "start_date": exam.start_date.as_ref().map(|d| d.date.to_rfc3339()),
+                "due_date": exam.due_date.as_ref().map(|d| d.date.to_rfc3339()),
+                "importance_level": exam.due_date.as_ref().map(|d| importance_level(d, policy)),
+                "local_status": local.and_then(|local| local.status),
+                "note": local.and_then(|local| local.note.as_ref())
+            }),
+            Assignment::Todo(todo) => json!({
+                "type": "todo",
+                "id": todo.id,
+                "course_id": todo.course_id,
+                "course_title": todo.course_title,
+                "title": todo.title,
+                "done": todo.done,
+                "due_date": todo.due(client.timezone).map(|d| d.date.to_rfc3339()),
+                "importance_level": todo.due(client.timezone).map(|d| importance_level(&d, policy)),
+                "local_status": local.and_then(|local| local.status),
+                "note": local.and_then(|local| local.note.as_ref())
+            }),
+        }
+    }
+}
+
+impl Queryable for Assignment<'_> {
+    fn id(&self) -> &str {
+        match self {
+            Assignment::Report(_, report) => &report.id,
+            Assignment::Exam(_, exam) => &exam.id,
+            Assignment::Todo(todo) => todo.id(),
+        }
+    }
+
+    fn kind(&self) -> AssignmentKind {
+        match self {
+            Assignment::Report(course, report) => (*course, *report).kind(),
+            Assignment::Exam(course, exam) => (*course, *exam).kind(),
+            Assignment::Todo(todo) => todo.kind(),
+        }
+    }
+
+    fn course_id(&self) -> &str {
+        match self {
+            Assignment::Report(course, _) | Assignment::Exam(course, _) => &course.id,
+            Assignment::Todo(todo) => todo.course_id(),
+        }
+    }
+
+    fn course_title(&self) -> &str {
+        match self {
+            Assignment::Report(course, _) | Assignment::Exam(course, _) => &course.title,
+            Assignment::Todo(todo) => todo.course_title(),
+        }
+    }
+
+    fn title(&self) -> &str {
+        match self {
+            Assignment::Report(_, report) => &report.title,
+            Assignment::Exam(_, exam) => &exam.title,
+            Assignment::Todo(todo) => &todo.title,
+        }
+    }
+
+    fn state(&self) -> AssignmentState {
+        match self {
+            Assignment::Report(course, report) => (*course, *report).state(),
+            Assignment::Exam(course, exam) => (*course, *exam).state(),
+            Assignment::Todo(todo) => todo.state(),
+        }
+    }
+
+    fn due_date(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Assignment::Report(course, report) => (*course, *report).due_date(),
+            Assignment::Exam(course, exam) => (*course, *exam).due_date(),
+            Assignment::Todo(todo) => todo.due_date,
+        }
+    }
+}
+
 impl ServerHandler for ManabaServer {
     async fn list_tools(
         &self,
@@ -93,12 +285,44 @@ impl ServerHandler for ManabaServer {
             Tool {
                 name: Cow::Borrowed("list_all_assignments"),
                 description: Cow::Borrowed(
-                    "List all assignments (reports and exams) across all courses",
+                    "List all assignments (reports and exams) across all courses and local todos, optionally filtered and sorted",
                 ),
                 input_schema: Arc::new(
                     json!({
                         "type": "object",
-                        "properties": {},
+                        "properties": {
+                            "course": {
+                                "type": "string",
+                                "description": "Case-insensitive substring of the course title or ID"
+                            },
+                            "due_before": {
+                                "type": "string",
+                                "description": "Only deadlines before this RFC 3339 time"
+                            },
+                            "due_after": {
+                                "type": "string",
+                                "description": "Only deadlines at or after this RFC 3339 time"
+                            },
+                            "states": {
+                                "type": "array",
+                                "items": { "type": "string", "enum": ["todo", "done", "closed", "waiting", "ignored"] },
+                                "description": "Only assignments in these states, after applying local marks from `manaba mark`"
+                            },
+                            "kinds": {
+                                "type": "array",
+                                "items": { "type": "string", "enum": ["report", "exam", "todo"] },
+                                "description": "Only assignments of these kinds"
+                            },
+                            "search": {
+                                "type": "string",
+                                "description": "Case-insensitive substring of the assignment title"
+                            },
+                            "sort": {
+                                "type": "string",
+                                "enum": ["due", "course", "title"],
+                                "description": "Sort order, keeping the course order when omitted"
+                            }
+                        },
                         "required": []
                     })
                     .as_object()
@@ -159,20 +383,15 @@ impl ServerHandler for ManabaServer {
                 let reports = client.get_reports(course).await.map_err(|e| {
                     rmcp::Error::internal_error(format!("Failed to get reports: {}", e), None)
                 })?;
+                let overlay = load_overlay();
+                let policy = load_urgency_policy();
                 let result = json!({
                     "course": {
                         "id": course.id,
                         "title": course.title
                     },
                     "reports": reports.iter().map(|report| {
-                        json!({
-                            "title": report.title,
-                            "submit_state": format!("{:?}", report.submit_state),
-                            "receptible_state": format!("{:?}", report.receptiable_state),
-                            "start_date": report.start_date.as_ref().map(|d| d.date.format("%Y-%m-%d %H:%M").to_string()),
-                            "due_date": report.due_date.as_ref().map(|d| d.date.format("%Y-%m-%d %H:%M").to_string()),
-                            "importance_level": report.due_date.as_ref().map(|d| format!("{:?}", d.importance_level))
-                        })
+                        Assignment::Report(course, report).to_json(overlay.get(&report.id), client, &policy)
                     }).collect::<Vec<_>>()
                 });
                 serde_json::to_string_pretty(&result).map_err(|e| {
@@ -198,20 +417,15 @@ impl ServerHandler for ManabaServer {
                 let exams = client.get_exams(course).await.map_err(|e| {
                     rmcp::Error::internal_error(format!("Failed to get exams: {}", e), None)
                 })?;
+                let overlay = load_overlay();
+                let policy = load_urgency_policy();
                 let result = json!({
                     "course": {
                         "id": course.id,
                         "title": course.title
                     },
                     "exams": exams.iter().map(|exam| {
-                        json!({
-                            "title": exam.title,
-                            "submit_state": format!("{:?}", exam.submit_state),
-                            "receptible_state": format!("{:?}", exam.receptiable_state),
-                            "start_date": exam.start_date.as_ref().map(|d| d.date.format("%Y-%m-%d %H:%M").to_string()),
-                            "due_date": exam.due_date.as_ref().map(|d| d.date.format("%Y-%m-%d %H:%M").to_string()),
-                            "importance_level": exam.due_date.as_ref().map(|d| format!("{:?}", d.importance_level))
-                        })
+                        Assignment::Exam(course, exam).to_json(overlay.get(&exam.id), client, &policy)
                     }).collect::<Vec<_>>()
                 });
                 serde_json::to_string_pretty(&result).map_err(|e| {
@@ -219,47 +433,61 @@ impl ServerHandler for ManabaServer {
                 })?
             }
             "list_all_assignments" => {
+                let query: AssignmentQuery = serde_json::from_value(serde_json::Value::Object(
+                    request.arguments.clone().unwrap_or_default(),
+                ))
+                .map_err(|e| rmcp::Error::invalid_params(e.to_string(), None))?;
+
                 let courses = client.get_courses().await.map_err(|e| {
                     rmcp::Error::internal_error(format!("Failed to get courses: {}", e), None)
                 })?;
-                let mut all_assignments = Vec::new();
-
-                for course in &courses {
-                    let reports = client.get_reports(course).await.map_err(|e| {
-                        rmcp::Error::internal_error(format!("Failed to get reports: {}", e), None)
-                    })?;
-                    for report in reports {
-                        all_assignments.push(json!({
-                                "type": "report",
-                                "course_id": course.id,
-                                "course_title": course.title,
-                                "title": report.title,
-                                "submit_state": format!("{:?}", report.submit_state),
-                                "receptible_state": format!("{:?}", report.receptiable_state),
-                                "start_date": report.start_date.as_ref().map(|d| d.date.format("%Y-%m-%d %H:%M").to_string()),
-                                "due_date": report.due_date.as_ref().map(|d| d.date.format("%Y-%m-%d %H:%M").to_string()),
-                                "importance_level": report.due_date.as_ref().map(|d| format!("{:?}", d.importance_level))
-                            }));
-                    }
-
-                    let exams = client.get_exams(course).await.map_err(|e| {
-                        rmcp::Error::internal_error(format!("Failed to get exams: {}", e), None)
-                    })?;
-                    for exam in exams {
-                        all_assignments.push(json!({
-                                "type": "exam",
-                                "course_id": course.id,
-                                "course_title": course.title,
-                                "title": exam.title,
-                                "submit_state": format!("{:?}", exam.submit_state),
-                                "receptible_state": format!("{:?}", exam.receptiable_state),
-                                "start_date": exam.start_date.as_ref().map(|d| d.date.format("%Y-%m-%d %H:%M").to_string()),
-                                "due_date": exam.due_date.as_ref().map(|d| d.date.format("%Y-%m-%d %H:%M").to_string()),
-                                "importance_level": exam.due_date.as_ref().map(|d| format!("{:?}", d.importance_level))
-                            }));
-                    }
+                let courses = courses
+                    .iter()
+                    .filter(|course| query.matches_course(course))
+                    .collect::<Vec<_>>();
+                let mut fetched = Vec::new();
+
+                for course in courses {
+                    let reports = if query.includes_kind(AssignmentKind::Report) {
+                        client.get_reports(course).await.map_err(|e| {
+                            rmcp::Error::internal_error(
+                                format!("Failed to get reports: {}", e),
+                                None,
+                            )
+                        })?
+                    } else {
+                        Vec::new()
+                    };
+
+                    let exams = if query.includes_kind(AssignmentKind::Exam) {
+                        client.get_exams(course).await.map_err(|e| {
+                            rmcp::Error::internal_error(format!("Failed to get exams: {}", e), None)
+                        })?
+                    } else {
+                        Vec::new()
+                    };
+
+                    fetched.push((course, reports, exams));
                 }
 
+                let todos = load_todos();
+                let assignments = fetched
+                    .iter()
+                    .flat_map(|(course, reports, exams)| {
+                        reports
+                            .iter()
+                            .map(|report| Assignment::Report(course, report))
+                            .chain(exams.iter().map(|exam| Assignment::Exam(course, exam)))
+                    })
+                    .chain(todos.iter().map(Assignment::Todo));
+                let overlay = load_overlay();
+                let policy = load_urgency_policy();
+                let all_assignments = query
+                    .apply(assignments.map(|assignment| overlay.wrap(assignment)))
+                    .iter()
+                    .map(|assignment| assignment.item.to_json(assignment.local, client, &policy))
+                    .collect::<Vec<_>>();
+
                 let result = json!({
                     "assignments": all_assignments
                 });
@@ -1,13 +1,27 @@
-use chrono::NaiveDateTime;
+use crate::clock::{Clock, SystemClock};
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssignmentKind {
+    Report,
+    Exam,
+    /// Personal item from the local [`crate::todo::TodoList`]
+    Todo,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AssignmentSubmitState {
     #[default]
     Todo,
     Done,
 }
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AssignmentReceptibleState {
     #[default]
     NotStarted,
@@ -17,49 +31,120 @@ pub enum AssignmentReceptibleState {
 
 #[derive(Debug)]
 pub struct AssignmentDate {
-    pub date: NaiveDateTime,
-    pub importance_level: AssignmentImportanceLevel,
+    pub date: DateTime<Tz>,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AssignmentImportanceLevel {
     None,
     Low,
     Medium,
     High,
+    /// Due by the end of today, only used when `UrgencyPolicy::due_today` is enabled
+    DueToday,
+    /// Past the deadline, only used when `UrgencyPolicy::overdue` is enabled
+    Overdue,
 }
 
-impl AssignmentDate {
-    pub fn new(date: &str) -> Self {
-        const DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
-        let date = NaiveDateTime::parse_from_str(date, DATE_FORMAT).unwrap();
-        let importance_level = AssignmentImportanceLevel::from(date);
+/// Thresholds used to classify deadlines into importance levels
+///
+/// Unset thresholds count calendar days, so that every deadline on the same day shares a level.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrgencyPolicy {
+    /// Deadlines within this many hours are `High`, within 3 calendar days when unset
+    pub high_hours: Option<u32>,
+    /// Deadlines within this many hours are `Medium`, within 7 calendar days when unset
+    pub medium_hours: Option<u32>,
+    /// Classify deadlines due by the end of today as `DueToday`
+    pub due_today: bool,
+    /// Classify past deadlines as `Overdue` instead of `None`
+    pub overdue: bool,
+}
+
+impl UrgencyPolicy {
+    /// Calendar days counted as `High` when `high_hours` is unset
+    pub const HIGH_DAYS: i64 = 3;
+    /// Calendar days counted as `Medium` when `medium_hours` is unset
+    pub const MEDIUM_DAYS: i64 = 7;
 
-        AssignmentDate {
-            date,
-            importance_level,
+    /// Check that `High` does not reach further than `Medium`
+    pub fn validate(&self) -> Result<(), String> {
+        match (self.high_hours, self.medium_hours) {
+            (Some(high), Some(medium)) if high > medium => Err(format!(
+                "high_hours ({high}) must not be greater than medium_hours ({medium})"
+            )),
+            _ => Ok(()),
         }
     }
-}
 
-impl From<NaiveDateTime> for AssignmentImportanceLevel {
-    fn from(date: NaiveDateTime) -> Self {
-        let today: chrono::NaiveDate = chrono::Local::now().date_naive();
-        let due_date: chrono::NaiveDate = date.date();
+    /// Calendar days and "due today" are judged in the time zone of `now`
+    pub fn importance_level<Tz1: TimeZone, Tz2: TimeZone>(
+        &self,
+        due_date: &DateTime<Tz1>,
+        now: &DateTime<Tz2>,
+    ) -> AssignmentImportanceLevel {
+        if due_date < now {
+            return if self.overdue {
+                AssignmentImportanceLevel::Overdue
+            } else {
+                AssignmentImportanceLevel::None
+            };
+        }
 
-        if due_date < today {
-            AssignmentImportanceLevel::None
-        } else {
-            let three_days_later = today + chrono::Duration::days(3);
-            let one_week_later = today + chrono::Duration::days(7);
+        let due_day = due_date.with_timezone(&now.timezone()).date_naive();
+        let today = now.date_naive();
 
-            if due_date <= three_days_later {
-                AssignmentImportanceLevel::High
-            } else if due_date <= one_week_later {
-                AssignmentImportanceLevel::Medium
-            } else {
-                AssignmentImportanceLevel::Low
-            }
+        if self.due_today && due_day == today {
+            return AssignmentImportanceLevel::DueToday;
         }
+
+        let remaining = due_date.clone().signed_duration_since(now.clone());
+        let within = |hours: Option<u32>, days: i64| match hours {
+            Some(hours) => remaining <= Duration::hours(hours.into()),
+            None => due_day <= today + Duration::days(days),
+        };
+
+        if within(self.high_hours, Self::HIGH_DAYS) {
+            AssignmentImportanceLevel::High
+        } else if within(self.medium_hours, Self::MEDIUM_DAYS) {
+            AssignmentImportanceLevel::Medium
+        } else {
+            AssignmentImportanceLevel::Low
+        }
+    }
+}
+
+impl AssignmentDate {
+    /// Parse a date shown by manaba, which is local time in `timezone`
+    pub fn new(date: &str, timezone: Tz) -> Self {
+        const DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+        let date = NaiveDateTime::parse_from_str(date, DATE_FORMAT).unwrap();
+        let date = timezone
+            .from_local_datetime(&date)
+            .earliest()
+            .unwrap_or_else(|| timezone.from_utc_datetime(&date));
+
+        AssignmentDate { date }
+    }
+
+    /// "Due today" is judged in the time zone of the deadline
+    pub fn importance_level(
+        &self,
+        policy: &UrgencyPolicy,
+        clock: &impl Clock,
+    ) -> AssignmentImportanceLevel {
+        policy.importance_level(
+            &self.date,
+            &clock.now().with_timezone(&self.date.timezone()),
+        )
+    }
+}
+
+/// Classify with the default policy against the current time
+impl<Tz1: TimeZone> From<DateTime<Tz1>> for AssignmentImportanceLevel {
+    fn from(date: DateTime<Tz1>) -> Self {
+        UrgencyPolicy::default().importance_level(&date, &SystemClock.now())
     }
 }
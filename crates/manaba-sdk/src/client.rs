@@ -2,12 +2,15 @@ use crate::{
     Cookie,
     error::{ManabaError, Result},
 };
+use chrono_tz::Tz;
 use reqwest::{IntoUrl, Method, RequestBuilder, header::HeaderMap};
 use scraper::{Html, Selector};
 use std::fmt::Display;
 
 pub struct Client {
     pub base_url: String,
+    /// Time zone the manaba instance displays dates in
+    pub timezone: Tz,
     pub(crate) reqwest_client: reqwest::Client,
     pub(crate) headers: HeaderMap,
 }
@@ -24,6 +27,7 @@ impl Client {
 
         let client = Client {
             base_url: base_url.to_owned(),
+            timezone: chrono_tz::Asia::Tokyo,
             reqwest_client,
             headers,
         };
@@ -35,6 +39,11 @@ impl Client {
         }
     }
 
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
     pub(crate) fn request<U>(&self, method: Method, url: U) -> RequestBuilder
     where
         U: IntoUrl + Display,
@@ -45,6 +54,17 @@ impl Client {
     }
 
     pub(crate) async fn get_html<U>(&self, method: reqwest::Method, url: U) -> Result<Html>
+    where
+        U: IntoUrl + Display,
+    {
+        let body = self.get_text(method, url).await?;
+
+        let document = Html::parse_document(&body);
+        Ok(document)
+    }
+
+    /// The body of a page, for callers that await again before parsing it
+    pub(crate) async fn get_text<U>(&self, method: reqwest::Method, url: U) -> Result<String>
     where
         U: IntoUrl + Display,
     {
@@ -55,13 +75,10 @@ impl Client {
             .await
             .map_err(ManabaError::SendRequestError)?;
 
-        let body = response
+        response
             .text()
             .await
-            .map_err(ManabaError::HtmlBodyParseError)?;
-
-        let document = Html::parse_document(&body);
-        Ok(document)
+            .map_err(ManabaError::HtmlBodyParseError)
     }
 
     pub async fn is_valid_cookie(&self) -> Result<bool> {
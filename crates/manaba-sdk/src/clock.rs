@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+
+/// Source of the current time, so urgency can be evaluated at any point in time
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The real time of the system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock stopped at a fixed time
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
@@ -10,45 +10,75 @@ impl Cookie {
         Cookie(cookie.to_owned())
     }
 
+    /// Load the cookie for `domain` from the first browser that has a `sessionid`
     pub fn load(domain: &str) -> Result<Self> {
-        let domains = vec![domain.to_owned()];
-        let domains = Some(domains);
-
-        let mut browsers = [
-            rookie::chrome,
-            rookie::chromium,
-            rookie::zen,
-            rookie::brave,
-            rookie::arc,
-        ];
-
-        for browser in browsers {
-            let mut cookies = match browser(domains.clone()) {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
+        BROWSERS
+            .iter()
+            .find_map(|&(name, browser)| read_browser(name, browser, domain).cookie)
+            .ok_or_else(|| ManabaError::LoadCookie("Cookie not found".to_owned()))
+    }
 
-            cookies
-                .iter_mut()
-                .for_each(|s| s.name = s.name.trim().to_string());
+    /// Read every supported browser, in the order [`Cookie::load`] tries them
+    pub fn probe(domain: &str) -> Vec<BrowserCookies> {
+        BROWSERS
+            .iter()
+            .map(|&(name, browser)| read_browser(name, browser, domain))
+            .collect()
+    }
+}
+
+type Browser = fn(Option<Vec<String>>) -> rookie::Result<Vec<rookie::enums::Cookie>>;
+
+const BROWSERS: [(&str, Browser); 5] = [
+    ("chrome", rookie::chrome),
+    ("chromium", rookie::chromium),
+    ("zen", rookie::zen),
+    ("brave", rookie::brave),
+    ("arc", rookie::arc),
+];
 
-            if cookies.is_empty() {
-                continue;
-            }
+/// Cookies a browser has for the manaba domain
+#[derive(Debug)]
+pub struct BrowserCookies {
+    pub browser: &'static str,
+    /// Why the browser could not be read, such as it not being installed
+    pub error: Option<String>,
+    pub count: usize,
+    /// Present when the browser has a `sessionid`
+    pub cookie: Option<Cookie>,
+}
+
+fn read_browser(name: &'static str, browser: Browser, domain: &str) -> BrowserCookies {
+    let mut cookies = match browser(Some(vec![domain.to_owned()])) {
+        Ok(cookies) => cookies,
+        Err(e) => {
+            return BrowserCookies {
+                browser: name,
+                error: Some(e.to_string()),
+                count: 0,
+                cookie: None,
+            };
+        }
+    };
 
-            if !cookies.iter().any(|v| v.name == "sessionid") {
-                continue;
-            }
+    cookies
+        .iter_mut()
+        .for_each(|s| s.name = s.name.trim().to_string());
 
-            let cookie = cookies
+    let cookie = cookies.iter().any(|v| v.name == "sessionid").then(|| {
+        Cookie(
+            cookies
                 .iter()
                 .map(|cookie| format!("{}={}", cookie.name, cookie.value))
                 .collect::<Vec<_>>()
-                .join(";");
-
-            return Ok(Cookie(cookie));
-        }
+                .join(";"),
+        )
+    });
 
-        Err(ManabaError::LoadCookie("Cookie not found".to_owned()))
+    BrowserCookies {
+        browser: name,
+        error: None,
+        count: cookies.len(),
+        cookie,
     }
 }
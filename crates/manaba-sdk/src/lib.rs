@@ -4,9 +4,24 @@ mod client;
 pub use client::Client;
 
 mod cookie;
-pub use cookie::Cookie;
+pub use cookie::{BrowserCookies, Cookie};
 
 mod scrape;
-pub use scrape::{course::Course, exam::Exam, report::Report};
+pub use scrape::{
+    check::SelectorCheck,
+    course::{AcademicTerm, Course, Term},
+    exam::Exam,
+    news::News,
+    page::PageText,
+    report::Report,
+    timetable::TimetableEntry,
+};
 
 pub mod assignment;
+pub mod clock;
+pub mod overlay;
+pub mod query;
+mod store;
+pub mod todo;
+
+pub use chrono_tz::Tz;
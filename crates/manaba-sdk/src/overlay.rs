@@ -0,0 +1,121 @@
+use crate::assignment::AssignmentKind;
+use crate::query::{AssignmentState, Queryable};
+use crate::store;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Progress tracked on this machine, which manaba knows nothing about
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalStatus {
+    /// Work has begun but nothing is submitted yet
+    Started,
+    /// Optional assignments that will never be submitted
+    Ignored,
+    /// Handed in outside manaba, such as on paper
+    DoneOffline,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LocalState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<LocalStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl LocalState {
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none() && self.note.is_none()
+    }
+}
+
+/// Local states keyed by assignment id, layered over what manaba reports
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Overlay(BTreeMap<String, LocalState>);
+
+impl Overlay {
+    /// `overlay.json` in the `manaba` data directory
+    pub fn default_path() -> Option<PathBuf> {
+        store::data_path("overlay.json")
+    }
+
+    /// Load the overlay at `path`, or an empty one when it does not exist yet
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        store::load(path)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        store::save(path, self)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&LocalState> {
+        self.0.get(id)
+    }
+
+    /// Replace the state of `id`, dropping it once it has neither a status nor a note
+    pub fn set(&mut self, id: &str, state: LocalState) {
+        if state.is_empty() {
+            self.0.remove(id);
+        } else {
+            self.0.insert(id.to_owned(), state);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &LocalState)> {
+        self.0.iter()
+    }
+
+    /// Wrap `item` so its state reflects the local status
+    pub fn wrap<T: Queryable>(&self, item: T) -> Overlaid<'_, T> {
+        let local = self.get(item.id());
+        Overlaid { item, local }
+    }
+}
+
+/// State of an assignment after applying `status`
+pub fn apply_status(state: AssignmentState, status: Option<LocalStatus>) -> AssignmentState {
+    match status {
+        Some(LocalStatus::Ignored) => AssignmentState::Ignored,
+        Some(LocalStatus::DoneOffline) => AssignmentState::Done,
+        Some(LocalStatus::Started) | None => state,
+    }
+}
+
+/// An assignment together with its local state
+pub struct Overlaid<'a, T> {
+    pub item: T,
+    pub local: Option<&'a LocalState>,
+}
+
+impl<T: Queryable> Queryable for Overlaid<'_, T> {
+    fn id(&self) -> &str {
+        self.item.id()
+    }
+
+    fn kind(&self) -> AssignmentKind {
+        self.item.kind()
+    }
+
+    fn course_id(&self) -> &str {
+        self.item.course_id()
+    }
+
+    fn course_title(&self) -> &str {
+        self.item.course_title()
+    }
+
+    fn title(&self) -> &str {
+        self.item.title()
+    }
+
+    fn state(&self) -> AssignmentState {
+        apply_status(self.item.state(), self.local.and_then(|local| local.status))
+    }
+
+    fn due_date(&self) -> Option<DateTime<Utc>> {
+        self.item.due_date()
+    }
+}
@@ -0,0 +1,255 @@
+use crate::assignment::{AssignmentKind, AssignmentReceptibleState, AssignmentSubmitState};
+use crate::{Course, Exam, Report};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Combined submit and receptible state of an assignment
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssignmentState {
+    /// Open and not submitted yet
+    Todo,
+    Done,
+    /// Closed without a submission
+    Closed,
+    /// Not open yet
+    Waiting,
+    /// Marked as ignored in the local overlay
+    Ignored,
+}
+
+impl AssignmentState {
+    pub fn new(
+        submit_state: AssignmentSubmitState,
+        receptible_state: AssignmentReceptibleState,
+    ) -> Self {
+        match (submit_state, receptible_state) {
+            (_, AssignmentReceptibleState::NotStarted) => AssignmentState::Waiting,
+            (AssignmentSubmitState::Done, _) => AssignmentState::Done,
+            (AssignmentSubmitState::Todo, AssignmentReceptibleState::Open) => AssignmentState::Todo,
+            (AssignmentSubmitState::Todo, AssignmentReceptibleState::Closed) => {
+                AssignmentState::Closed
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    /// Earliest deadline first, without a deadline last
+    Due,
+    /// By course title, then by deadline
+    Course,
+    Title,
+}
+
+/// An assignment as seen by [`AssignmentQuery`]
+pub trait Queryable {
+    fn id(&self) -> &str;
+    fn kind(&self) -> AssignmentKind;
+    fn course_id(&self) -> &str;
+    fn course_title(&self) -> &str;
+    fn title(&self) -> &str;
+    fn state(&self) -> AssignmentState;
+    fn due_date(&self) -> Option<DateTime<Utc>>;
+}
+
+/// Filters and sort order shared by the CLI and the MCP server
+///
+/// Empty fields match everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssignmentQuery {
+    /// Case-insensitive substring of the course title or id
+    pub course: Option<String>,
+    /// Only deadlines strictly before this time
+    pub due_before: Option<DateTime<Utc>>,
+    /// Only deadlines at or after this time
+    pub due_after: Option<DateTime<Utc>>,
+    pub states: Vec<AssignmentState>,
+    pub kinds: Vec<AssignmentKind>,
+    /// Case-insensitive substring of the assignment title
+    pub search: Option<String>,
+    pub sort: Option<SortKey>,
+}
+
+impl AssignmentQuery {
+    pub fn matches(&self, item: &impl Queryable) -> bool {
+        if !self.matches_course_fields(item.course_id(), item.course_title()) {
+            return false;
+        }
+
+        if !self.includes_kind(item.kind()) {
+            return false;
+        }
+
+        if !self.states.is_empty() && !self.states.contains(&item.state()) {
+            return false;
+        }
+
+        if let Some(search) = &self.search
+            && !contains_ignore_case(item.title(), search)
+        {
+            return false;
+        }
+
+        if self.due_before.is_some() || self.due_after.is_some() {
+            let Some(due_date) = item.due_date() else {
+                return false;
+            };
+            if self.due_before.is_some_and(|before| due_date >= before)
+                || self.due_after.is_some_and(|after| due_date < after)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Whether assignments of `course` can match, to skip fetching the others
+    pub fn matches_course(&self, course: &Course) -> bool {
+        self.matches_course_fields(&course.id, &course.title)
+    }
+
+    pub fn includes_kind(&self, kind: AssignmentKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+
+    /// Stable sort by `sort`, keeping the order when it is unset
+    pub fn sort<T: Queryable>(&self, items: &mut [T]) {
+        let Some(sort) = self.sort else {
+            return;
+        };
+
+        items.sort_by(|a, b| match sort {
+            SortKey::Due => compare_due(a, b),
+            SortKey::Course => a
+                .course_title()
+                .cmp(b.course_title())
+                .then_with(|| compare_due(a, b)),
+            SortKey::Title => a.title().cmp(b.title()),
+        });
+    }
+
+    /// Filter and sort `items`
+    pub fn apply<T: Queryable>(&self, items: impl IntoIterator<Item = T>) -> Vec<T> {
+        let mut items = items
+            .into_iter()
+            .filter(|item| self.matches(item))
+            .collect::<Vec<_>>();
+        self.sort(&mut items);
+        items
+    }
+
+    fn matches_course_fields(&self, id: &str, title: &str) -> bool {
+        self.course.as_ref().is_none_or(|pattern| {
+            contains_ignore_case(title, pattern) || contains_ignore_case(id, pattern)
+        })
+    }
+}
+
+impl<T: Queryable + ?Sized> Queryable for &T {
+    fn id(&self) -> &str {
+        (**self).id()
+    }
+
+    fn kind(&self) -> AssignmentKind {
+        (**self).kind()
+    }
+
+    fn course_id(&self) -> &str {
+        (**self).course_id()
+    }
+
+    fn course_title(&self) -> &str {
+        (**self).course_title()
+    }
+
+    fn title(&self) -> &str {
+        (**self).title()
+    }
+
+    fn state(&self) -> AssignmentState {
+        (**self).state()
+    }
+
+    fn due_date(&self) -> Option<DateTime<Utc>> {
+        (**self).due_date()
+    }
+}
+
+fn compare_due(a: &impl Queryable, b: &impl Queryable) -> Ordering {
+    match (a.due_date(), b.due_date()) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn contains_ignore_case(text: &str, pattern: &str) -> bool {
+    text.to_lowercase().contains(&pattern.to_lowercase())
+}
+
+impl Queryable for (&Course, &Report) {
+    fn id(&self) -> &str {
+        &self.1.id
+    }
+
+    fn kind(&self) -> AssignmentKind {
+        AssignmentKind::Report
+    }
+
+    fn course_id(&self) -> &str {
+        &self.0.id
+    }
+
+    fn course_title(&self) -> &str {
+        &self.0.title
+    }
+
+    fn title(&self) -> &str {
+        &self.1.title
+    }
+
+    fn state(&self) -> AssignmentState {
+        AssignmentState::new(self.1.submit_state, self.1.receptiable_state)
+    }
+
+    fn due_date(&self) -> Option<DateTime<Utc>> {
+        self.1.due_date.as_ref().map(|d| d.date.with_timezone(&Utc))
+    }
+}
+
+impl Queryable for (&Course, &Exam) {
+    fn id(&self) -> &str {
+        &self.1.id
+    }
+
+    fn kind(&self) -> AssignmentKind {
+        AssignmentKind::Exam
+    }
+
+    fn course_id(&self) -> &str {
+        &self.0.id
+    }
+
+    fn course_title(&self) -> &str {
+        &self.0.title
+    }
+
+    fn title(&self) -> &str {
+        &self.1.title
+    }
+
+    fn state(&self) -> AssignmentState {
+        AssignmentState::new(self.1.submit_state, self.1.receptiable_state)
+    }
+
+    fn due_date(&self) -> Option<DateTime<Utc>> {
+        self.1.due_date.as_ref().map(|d| d.date.with_timezone(&Utc))
+    }
+}
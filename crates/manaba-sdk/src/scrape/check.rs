@@ -0,0 +1,84 @@
+use super::{ROW_SELECTOR, course, news, page, timetable};
+use crate::{Client, Course};
+use scraper::{Html, Selector};
+
+/// How many elements a selector the scrapers rely on matched on the live page
+#[derive(Debug)]
+pub struct SelectorCheck {
+    pub scraper: &'static str,
+    pub path: String,
+    pub selector: &'static str,
+    /// The error message when the page could not be loaded
+    pub matches: Result<usize, String>,
+}
+
+impl Client {
+    /// Run the scraper selectors against the live pages, using `course` for its top page and
+    /// its report, exam and news lists
+    ///
+    /// No matches on a course page can also mean that the course has nothing to list.
+    pub async fn check_selectors(&self, course: Option<&Course>) -> Vec<SelectorCheck> {
+        // manaba remembers the view of `home_course` loaded last, so the user's one is restored
+        let original_view = self.course_view().await.ok();
+        let mut pages = vec![
+            (
+                "timetable",
+                "home_course?chglistformat=timetable".to_owned(),
+                vec![timetable::TABLE_SELECTOR],
+            ),
+            (
+                "courses",
+                "home_course?chglistformat=list".to_owned(),
+                vec![course::LIST_SELECTOR, course::LINK_SELECTOR],
+            ),
+        ];
+        if let Some(course) = course {
+            pages.extend([
+                (
+                    "reports",
+                    format!("{}_report", course.id),
+                    vec![ROW_SELECTOR],
+                ),
+                ("exams", format!("{}_query", course.id), vec![ROW_SELECTOR]),
+                (
+                    "news",
+                    format!("{}_news", course.id),
+                    vec![ROW_SELECTOR, news::LINK_SELECTOR],
+                ),
+                ("pages", course.id.clone(), vec![page::CONTENT_SELECTOR]),
+            ]);
+        }
+
+        let mut checks = Vec::new();
+        for (scraper, path, selectors) in pages {
+            let html = self
+                .get_html(reqwest::Method::GET, path.as_str())
+                .await
+                .map_err(|e| e.to_string());
+
+            for selector in selectors {
+                checks.push(SelectorCheck {
+                    scraper,
+                    path: path.clone(),
+                    selector,
+                    matches: html
+                        .as_ref()
+                        .map_err(Clone::clone)
+                        .and_then(|html| count(html, selector)),
+                });
+            }
+        }
+
+        if let Some(view) = original_view {
+            // The checks are complete even when restoring the view fails
+            let _ = self.set_course_view(view).await;
+        }
+
+        checks
+    }
+}
+
+fn count(html: &Html, selector: &str) -> Result<usize, String> {
+    let selector = Selector::parse(selector).map_err(|e| e.to_string())?;
+    Ok(html.select(&selector).count())
+}
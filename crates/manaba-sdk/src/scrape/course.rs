@@ -1,41 +1,291 @@
+use super::timetable::TABLE_SELECTOR;
 use crate::Client;
 use crate::error::Result;
-use scraper::Selector;
+use chrono::{Datelike as _, NaiveDate};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Table of the list view of `home_course`
+pub(crate) const LIST_SELECTOR: &str = "table.stdlist.courselist, table.courselist";
+/// Links to a course, found in every view of `home_course`
+pub(crate) const LINK_SELECTOR: &str = "a[href^='course_']";
+
+#[derive(Debug, Clone, Default)]
 pub struct Course {
     pub title: String,
     pub id: String,
+    /// Academic year, which starts in April
+    pub year: Option<i32>,
+    pub term: Option<Term>,
+    pub instructors: Vec<String>,
+    /// Day and period as shown by manaba, e.g. `月1`
+    pub schedule: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Term {
+    /// 前期 / 春セメスター
+    First,
+    /// 後期 / 秋セメスター
+    Second,
+    /// 通年
+    FullYear,
+}
+
+impl Term {
+    fn parse(text: &str) -> Option<Self> {
+        if text.contains("通年") {
+            Some(Term::FullYear)
+        } else if text.contains("前期") || text.contains('春') {
+            Some(Term::First)
+        } else if text.contains("後期") || text.contains('秋') {
+            Some(Term::Second)
+        } else {
+            None
+        }
+    }
+}
+
+/// A term of a specific academic year
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct AcademicTerm {
+    pub year: i32,
+    pub term: Term,
+}
+
+impl AcademicTerm {
+    /// The term `date` falls in, with the first term from April to September
+    pub fn at(date: NaiveDate) -> Self {
+        let year = if date.month() >= 4 {
+            date.year()
+        } else {
+            date.year() - 1
+        };
+        let term = if (4..=9).contains(&date.month()) {
+            Term::First
+        } else {
+            Term::Second
+        };
+
+        AcademicTerm { year, term }
+    }
 }
 
 impl Course {
     pub fn url(&self, base_url: &str) -> String {
         format!("{}/{}", base_url, self.id)
     }
+
+    /// Whether the course is held in `academic_term`, treating unknown years and terms as held
+    pub fn is_held_in(&self, academic_term: &AcademicTerm) -> bool {
+        self.year.is_none_or(|year| year == academic_term.year)
+            && self
+                .term
+                .is_none_or(|term| term == academic_term.term || term == Term::FullYear)
+    }
+
+    /// Parse the courses of a `home_course` page
+    ///
+    /// The list view has the most details, other views only give titles and ids.
+    pub fn from_html(html: &str) -> Result<Vec<Course>> {
+        let html = Html::parse_document(html);
+
+        match parse_course_list(&html)? {
+            Some(courses) => Ok(courses),
+            None => parse_course_links(&html),
+        }
+    }
+}
+
+/// Views of `home_course`, which manaba remembers as a setting of the user
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum CourseView {
+    Thumbnail,
+    List,
+    Timetable,
+}
+
+impl CourseView {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            CourseView::Thumbnail => "thumbnail",
+            CourseView::List => "list",
+            CourseView::Timetable => "timetable",
+        }
+    }
+
+    /// The view a `home_course` page is shown in
+    fn of(body: &str) -> Result<Self> {
+        let html = Html::parse_document(body);
+        let list_selector = Selector::parse(LIST_SELECTOR)?;
+        let timetable_selector = Selector::parse(TABLE_SELECTOR)?;
+
+        Ok(if html.select(&list_selector).next().is_some() {
+            CourseView::List
+        } else if html.select(&timetable_selector).next().is_some() {
+            CourseView::Timetable
+        } else {
+            CourseView::Thumbnail
+        })
+    }
 }
 
 impl Client {
     pub async fn get_courses(&self) -> Result<Vec<Course>> {
-        let html = self.get_html(reqwest::Method::GET, "home_course").await?;
-
-        let selector = Selector::parse("a[href^='course_']").unwrap();
-        let course_elements = html.select(&selector);
-
-        let mut all_courses = HashMap::new();
-        for course_element in course_elements {
-            if let Some(href) = course_element.attr("href") {
-                all_courses
-                    .entry(href.to_string())
-                    .or_insert(course_element.inner_html());
-            }
+        let body = self.get_course_view(CourseView::List).await?;
+
+        Course::from_html(&body)
+    }
+
+    /// The view of `home_course` the user has chosen
+    pub(crate) async fn course_view(&self) -> Result<CourseView> {
+        let body = self.get_text(reqwest::Method::GET, "home_course").await?;
+
+        CourseView::of(&body)
+    }
+
+    /// Show `home_course` in `view`, which manaba keeps for the next visits
+    pub(crate) async fn set_course_view(&self, view: CourseView) -> Result<String> {
+        let path = format!("home_course?chglistformat={}", view.name());
+
+        self.get_text(reqwest::Method::GET, path.as_str()).await
+    }
+
+    /// The body of `home_course` in `view`, leaving the view the user has chosen unchanged
+    pub(crate) async fn get_course_view(&self, view: CourseView) -> Result<String> {
+        let body = self.get_text(reqwest::Method::GET, "home_course").await?;
+        let original = CourseView::of(&body)?;
+        if original == view {
+            return Ok(body);
         }
 
-        let courses = all_courses
-            .into_iter()
-            .filter(|(_, v)| v.chars().take(5).all(|c| c.is_numeric()))
-            .map(|(id, title)| Course { title, id })
-            .collect::<Vec<Course>>();
+        let body = self.set_course_view(view).await?;
+        self.set_course_view(original).await?;
+
+        Ok(body)
+    }
+}
+
+/// Parse the list view, locating columns by their header names
+fn parse_course_list(html: &Html) -> Result<Option<Vec<Course>>> {
+    let table_selector = Selector::parse(LIST_SELECTOR)?;
+    let Some(table) = html.select(&table_selector).next() else {
+        return Ok(None);
+    };
+
+    let row_selector = Selector::parse("tr")?;
+    let header_selector = Selector::parse("th")?;
+    let cell_selector = Selector::parse("td")?;
+    let link_selector = Selector::parse(LINK_SELECTOR)?;
+
+    let mut rows = table.select(&row_selector);
+    let Some(header) = rows.next() else {
+        return Ok(None);
+    };
+
+    let columns = header
+        .select(&header_selector)
+        .map(text)
+        .collect::<Vec<_>>();
+    let column = |keywords: &[&str]| {
+        columns
+            .iter()
+            .position(|column| keywords.iter().any(|keyword| column.contains(keyword)))
+    };
+
+    let year_column = column(&["年度", "Year"]);
+    let term_column = column(&["学期", "開講", "Term", "Semester"]);
+    let schedule_column = column(&["曜日", "時限", "Day"]);
+    let instructor_column = column(&["担当", "教員", "Instructor"]);
+    let status_column = column(&["状態", "Status"]);
+
+    let courses = rows
+        .filter_map(|row| {
+            let cells = row.select(&cell_selector).collect::<Vec<_>>();
+            let cell_text = |column: Option<usize>| {
+                column
+                    .and_then(|column| cells.get(column))
+                    .map(|cell| text(*cell))
+                    .filter(|text| !text.is_empty())
+            };
 
-        Ok(courses)
+            let link = row.select(&link_selector).next()?;
+            let id = link.attr("href")?.to_owned();
+
+            let year_text = cell_text(year_column);
+            let year = year_text.as_deref().and_then(parse_year);
+            let term = cell_text(term_column)
+                .or(year_text)
+                .as_deref()
+                .and_then(Term::parse);
+
+            // Names are separated by line breaks or punctuation depending on the course
+            let instructors = instructor_column
+                .and_then(|column| cells.get(column))
+                .map(|cell| {
+                    cell.text()
+                        .flat_map(|text| text.split(['、', ',', '/', '\n']))
+                        .map(|name| name.trim().to_owned())
+                        .filter(|name| !name.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Some(Course {
+                title: text(link),
+                id,
+                year,
+                term,
+                instructors,
+                schedule: cell_text(schedule_column),
+                status: cell_text(status_column),
+            })
+        })
+        .collect();
+
+    Ok(Some(courses))
+}
+
+/// Fallback for other views, keeping only titles that start with a course code
+fn parse_course_links(html: &Html) -> Result<Vec<Course>> {
+    let selector = Selector::parse(LINK_SELECTOR)?;
+    let course_elements = html.select(&selector);
+
+    let mut all_courses = HashMap::new();
+    for course_element in course_elements {
+        if let Some(href) = course_element.attr("href") {
+            all_courses
+                .entry(href.to_string())
+                .or_insert(course_element.inner_html());
+        }
     }
+
+    let courses = all_courses
+        .into_iter()
+        .filter(|(_, v)| v.chars().take(5).all(|c| c.is_numeric()))
+        .map(|(id, title)| Course {
+            title,
+            id,
+            ..Default::default()
+        })
+        .collect::<Vec<Course>>();
+
+    Ok(courses)
+}
+
+fn parse_year(text: &str) -> Option<i32> {
+    let digits = text
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+
+    digits.parse().ok().filter(|year| *year >= 1900)
+}
+
+fn text(element: ElementRef) -> String {
+    element.text().collect::<String>().trim().to_owned()
 }
@@ -1,10 +1,11 @@
 use crate::Client;
 use crate::assignment::{AssignmentDate, AssignmentReceptibleState, AssignmentSubmitState};
-use crate::{Course, error::Result};
+use crate::{Course, error::Result, scrape::ROW_SELECTOR};
 use reqwest::Method;
 use scraper::Selector;
 
 pub struct Exam {
+    pub id: String,
     pub title: String,
     pub submit_state: AssignmentSubmitState,
     pub receptiable_state: AssignmentReceptibleState,
@@ -12,12 +13,18 @@ pub struct Exam {
     pub due_date: Option<AssignmentDate>,
 }
 
+impl Exam {
+    pub fn url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self.id)
+    }
+}
+
 impl Client {
     pub async fn get_exams(&self, course: &Course) -> Result<Vec<Exam>> {
         let url = format!("{}_query", course.id);
         let html = self.get_html(Method::GET, url).await?;
 
-        let selector = Selector::parse("table.stdlist tr:not(.title)").unwrap();
+        let selector = Selector::parse(ROW_SELECTOR).unwrap();
         let exam_element = html.select(&selector);
 
         let exams = exam_element
@@ -25,11 +32,15 @@ impl Client {
                 let selector = Selector::parse("td").unwrap();
                 let mut rows = report_element.select(&selector);
 
-                let title = {
+                let (id, title) = {
                     let row = rows.next().unwrap();
                     let selector = Selector::parse("a").unwrap();
                     let report_title_element = row.select(&selector).next().unwrap();
-                    report_title_element.inner_html()
+                    let id = report_title_element
+                        .attr("href")
+                        .unwrap_or_default()
+                        .to_owned();
+                    (id, report_title_element.inner_html())
                 };
 
                 let (receptiable_state, submit_state) = {
@@ -62,7 +73,7 @@ impl Client {
                     if date.is_empty() {
                         None
                     } else {
-                        Some(AssignmentDate::new(&date))
+                        Some(AssignmentDate::new(&date, self.timezone))
                     }
                 };
 
@@ -72,11 +83,12 @@ impl Client {
                     if date.is_empty() {
                         None
                     } else {
-                        Some(AssignmentDate::new(&date))
+                        Some(AssignmentDate::new(&date, self.timezone))
                     }
                 };
 
                 Exam {
+                    id,
                     title,
                     receptiable_state,
                     submit_state,
@@ -1,3 +1,10 @@
+pub mod check;
 pub mod course;
 pub mod exam;
+pub mod news;
+pub mod page;
 pub mod report;
+pub mod timetable;
+
+/// Rows of the report, exam and news lists of a course
+pub(crate) const ROW_SELECTOR: &str = "table.stdlist tr:not(.title)";
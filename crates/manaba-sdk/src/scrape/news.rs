@@ -0,0 +1,58 @@
+use crate::error::Result;
+use crate::{Client, Course, scrape::ROW_SELECTOR};
+use chrono::{DateTime, NaiveDateTime, TimeZone as _};
+use chrono_tz::Tz;
+use scraper::Selector;
+
+/// Links to a single news item
+pub(crate) const LINK_SELECTOR: &str = "a[href*='_news_']";
+
+#[derive(Debug)]
+pub struct News {
+    pub id: String,
+    pub title: String,
+    pub posted_at: Option<DateTime<Tz>>,
+}
+
+impl News {
+    pub fn url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self.id)
+    }
+}
+
+impl Client {
+    pub async fn get_news(&self, course: &Course) -> Result<Vec<News>> {
+        const DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+        let url = format!("{}_news", course.id);
+        let html = self.get_html(reqwest::Method::GET, url).await?;
+
+        let selector = Selector::parse(ROW_SELECTOR)?;
+        let news_elements = html.select(&selector);
+
+        let link_selector = Selector::parse(LINK_SELECTOR)?;
+        let row_selector = Selector::parse("td")?;
+
+        let news = news_elements
+            .filter_map(|news_element| {
+                let link = news_element.select(&link_selector).next()?;
+                let id = link.attr("href")?.to_owned();
+                let title = link.text().collect::<String>().trim().to_owned();
+
+                let posted_at = news_element.select(&row_selector).find_map(|row| {
+                    let text = row.text().collect::<String>();
+                    let date = NaiveDateTime::parse_from_str(text.trim(), DATE_FORMAT).ok()?;
+                    self.timezone.from_local_datetime(&date).earliest()
+                });
+
+                Some(News {
+                    id,
+                    title,
+                    posted_at,
+                })
+            })
+            .collect::<Vec<News>>();
+
+        Ok(news)
+    }
+}
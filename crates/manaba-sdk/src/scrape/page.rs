@@ -0,0 +1,86 @@
+use crate::Client;
+use crate::error::Result;
+use scraper::{ElementRef, Html, Selector};
+
+/// Main column of course, report, exam and news pages
+pub(crate) const CONTENT_SELECTOR: &str = "div.contentbody-left, div.contentbody-l";
+
+/// Elements whose content starts on a new line
+const BLOCK_ELEMENTS: [&str; 18] = [
+    "br", "p", "div", "table", "tr", "th", "td", "ul", "ol", "li", "dl", "dt", "dd", "h1", "h2",
+    "h3", "h4", "h5",
+];
+
+/// Readable text of a page, such as the description of an assignment or the body of news
+#[derive(Debug, Clone, Default)]
+pub struct PageText {
+    /// Non-empty lines of the main column, or of the whole body when there is none
+    pub lines: Vec<String>,
+}
+
+impl PageText {
+    /// Extract the text of a saved page, with one line per block element
+    pub fn from_html(html: &str) -> Result<Self> {
+        let html = Html::parse_document(html);
+
+        let content_selector = Selector::parse(CONTENT_SELECTOR)?;
+        let body_selector = Selector::parse("body")?;
+        let root = html
+            .select(&content_selector)
+            .next()
+            .or_else(|| html.select(&body_selector).next());
+
+        let mut lines = Vec::new();
+        if let Some(root) = root {
+            let mut line = String::new();
+            push_text(root, &mut lines, &mut line);
+            flush(&mut lines, &mut line);
+        }
+
+        Ok(PageText { lines })
+    }
+}
+
+impl Client {
+    /// Fetch the text of the page at `id`, such as `course_12345` or `course_12345_report_6789`
+    pub async fn get_page_text(&self, id: &str) -> Result<PageText> {
+        let body = self.get_text(reqwest::Method::GET, id).await?;
+        PageText::from_html(&body)
+    }
+}
+
+fn push_text(element: ElementRef, lines: &mut Vec<String>, line: &mut String) {
+    for child in element.children() {
+        if let Some(text) = child.value().as_text() {
+            for c in text.chars() {
+                if !c.is_whitespace() {
+                    line.push(c);
+                } else if !line.is_empty() && !line.ends_with(' ') {
+                    line.push(' ');
+                }
+            }
+        } else if let Some(child) = ElementRef::wrap(child) {
+            let name = child.value().name();
+            if matches!(name, "script" | "style") {
+                continue;
+            }
+
+            let is_block = BLOCK_ELEMENTS.contains(&name);
+            if is_block {
+                flush(lines, line);
+            }
+            push_text(child, lines, line);
+            if is_block {
+                flush(lines, line);
+            }
+        }
+    }
+}
+
+fn flush(lines: &mut Vec<String>, line: &mut String) {
+    let text = line.trim_end();
+    if !text.is_empty() {
+        lines.push(text.to_owned());
+    }
+    line.clear();
+}
@@ -1,10 +1,11 @@
 use crate::assignment::{AssignmentDate, AssignmentReceptibleState, AssignmentSubmitState};
 use crate::error::Result;
-use crate::{Client, Course};
+use crate::{Client, Course, scrape::ROW_SELECTOR};
 use scraper::Selector;
 
 #[derive(Debug)]
 pub struct Report {
+    pub id: String,
     pub title: String,
     pub submit_state: AssignmentSubmitState,
     pub receptiable_state: AssignmentReceptibleState,
@@ -12,12 +13,18 @@ pub struct Report {
     pub due_date: Option<AssignmentDate>,
 }
 
+impl Report {
+    pub fn url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self.id)
+    }
+}
+
 impl Client {
     pub async fn get_reports(&self, course: &Course) -> Result<Vec<Report>> {
         let url = format!("{}_report", course.id);
         let html = self.get_html(reqwest::Method::GET, url).await?;
 
-        let selector = Selector::parse("table.stdlist tr:not(.title)")?;
+        let selector = Selector::parse(ROW_SELECTOR)?;
         let reports_element = html.select(&selector);
 
         let reports = reports_element
@@ -25,11 +32,15 @@ impl Client {
                 let selector = Selector::parse("td").unwrap();
                 let mut rows = report_element.select(&selector);
 
-                let title = {
+                let (id, title) = {
                     let row = rows.next().unwrap();
                     let selector = Selector::parse("a").unwrap();
                     let report_title_element = row.select(&selector).next().unwrap();
-                    report_title_element.inner_html()
+                    let id = report_title_element
+                        .attr("href")
+                        .unwrap_or_default()
+                        .to_owned();
+                    (id, report_title_element.inner_html())
                 };
 
                 let (receptiable_state, submit_state) = {
@@ -66,7 +77,7 @@ impl Client {
                     if date.is_empty() {
                         None
                     } else {
-                        Some(AssignmentDate::new(&date))
+                        Some(AssignmentDate::new(&date, self.timezone))
                     }
                 };
 
@@ -76,11 +87,12 @@ impl Client {
                     if date.is_empty() {
                         None
                     } else {
-                        Some(AssignmentDate::new(&date))
+                        Some(AssignmentDate::new(&date, self.timezone))
                     }
                 };
 
                 Report {
+                    id,
                     title,
                     receptiable_state,
                     submit_state,
@@ -0,0 +1,124 @@
+use super::course::CourseView;
+use crate::error::Result;
+use crate::{Client, Course};
+use chrono::Weekday;
+use scraper::{ElementRef, Html, Selector};
+
+/// Table of the timetable view of `home_course`
+pub(crate) const TABLE_SELECTOR: &str = "table.stdlist.coursetable, table.coursetable";
+
+#[derive(Debug, Clone)]
+pub struct TimetableEntry {
+    pub weekday: Weekday,
+    pub period: u32,
+    pub course: Course,
+    pub room: Option<String>,
+}
+
+impl TimetableEntry {
+    /// Parse the timetable view of a `home_course` page, which has no entries in other views
+    pub fn from_html(html: &str) -> Result<Vec<TimetableEntry>> {
+        parse_timetable(&Html::parse_document(html))
+    }
+}
+
+impl Client {
+    /// Scrape the weekly timetable view of `home_course`
+    pub async fn get_timetable(&self) -> Result<Vec<TimetableEntry>> {
+        let body = self.get_course_view(CourseView::Timetable).await?;
+
+        TimetableEntry::from_html(&body)
+    }
+}
+
+fn parse_timetable(html: &Html) -> Result<Vec<TimetableEntry>> {
+    let table_selector = Selector::parse(TABLE_SELECTOR)?;
+    let Some(table) = html.select(&table_selector).next() else {
+        return Ok(Vec::new());
+    };
+
+    let row_selector = Selector::parse("tr")?;
+    let header_selector = Selector::parse("th, td")?;
+    let cell_selector = Selector::parse(":scope > td, :scope > th")?;
+    let link_selector = Selector::parse("a[href^='course_']")?;
+    let room_selector = Selector::parse(".courseweekly-fac, .coursetable-room")?;
+
+    let mut rows = table.select(&row_selector);
+
+    let weekdays = match rows.next() {
+        Some(header) => header
+            .select(&header_selector)
+            .map(|cell| parse_weekday(&text(cell)))
+            .collect::<Vec<_>>(),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut entries = Vec::new();
+
+    for row in rows {
+        let cells = row.select(&cell_selector).collect::<Vec<_>>();
+
+        let Some(period) = cells.first().and_then(|cell| parse_period(&text(*cell))) else {
+            continue;
+        };
+
+        for (i, cell) in cells.iter().enumerate().skip(1) {
+            let Some(weekday) = weekdays.get(i).copied().flatten() else {
+                continue;
+            };
+
+            let room = cell
+                .select(&room_selector)
+                .map(text)
+                .find(|room| !room.is_empty());
+
+            for link in cell.select(&link_selector) {
+                let Some(id) = link.attr("href") else {
+                    continue;
+                };
+
+                entries.push(TimetableEntry {
+                    weekday,
+                    period,
+                    course: Course {
+                        title: text(link),
+                        id: id.to_owned(),
+                        ..Default::default()
+                    },
+                    room: room.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+fn text(element: ElementRef) -> String {
+    element.text().collect::<String>().trim().to_owned()
+}
+
+fn parse_weekday(text: &str) -> Option<Weekday> {
+    match text.chars().next()? {
+        '月' | 'M' => Some(Weekday::Mon),
+        '火' | 'T' if !text.starts_with("Th") => Some(Weekday::Tue),
+        '水' | 'W' => Some(Weekday::Wed),
+        '木' | 'T' => Some(Weekday::Thu),
+        '金' | 'F' => Some(Weekday::Fri),
+        '土' => Some(Weekday::Sat),
+        'S' if text.starts_with("Sa") => Some(Weekday::Sat),
+        '日' | 'S' => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Period cells look like `1`, `1限` or `1時限`
+fn parse_period(text: &str) -> Option<u32> {
+    let digits = text
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+
+    digits.parse().ok()
+}
@@ -0,0 +1,24 @@
+use serde::{Serialize, de::DeserializeOwned};
+use std::path::{Path, PathBuf};
+
+/// `name` in the `manaba` data directory, shared by the CLI and the MCP server
+pub(crate) fn data_path(name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|data_dir| data_dir.join("manaba").join(name))
+}
+
+/// Load JSON at `path`, or the default value when it does not exist yet
+pub(crate) fn load<T: DeserializeOwned + Default>(path: &Path) -> std::io::Result<T> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(e),
+    }
+}
+
+pub(crate) fn save<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, serde_json::to_string_pretty(value)?)
+}
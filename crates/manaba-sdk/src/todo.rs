@@ -0,0 +1,123 @@
+use crate::Tz;
+use crate::assignment::{AssignmentDate, AssignmentKind};
+use crate::query::{AssignmentState, Queryable};
+use crate::store;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A self-imposed deadline kept next to the ones from manaba
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Todo {
+    /// `todo_` followed by a number, unique within a list
+    pub id: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub course_id: Option<String>,
+    /// Course title when the item was added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub course_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub done: bool,
+}
+
+impl Todo {
+    /// Deadline as an `AssignmentDate` in `timezone`, so urgency works the same as for assignments
+    pub fn due(&self, timezone: Tz) -> Option<AssignmentDate> {
+        self.due_date.map(|date| AssignmentDate {
+            date: date.with_timezone(&timezone),
+        })
+    }
+}
+
+/// Personal todo items, stored only on this machine
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TodoList {
+    next_id: u32,
+    todos: Vec<Todo>,
+}
+
+impl TodoList {
+    /// `todos.json` in the `manaba` data directory
+    pub fn default_path() -> Option<PathBuf> {
+        store::data_path("todos.json")
+    }
+
+    /// Load the list at `path`, or an empty one when it does not exist yet
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        store::load(path)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        store::save(path, self)
+    }
+
+    pub fn add(
+        &mut self,
+        title: String,
+        course: Option<(String, String)>,
+        due_date: Option<DateTime<Utc>>,
+    ) -> &Todo {
+        self.next_id += 1;
+        let (course_id, course_title) = course.unzip();
+
+        self.todos.push(Todo {
+            id: format!("todo_{}", self.next_id),
+            title,
+            course_id,
+            course_title,
+            due_date,
+            done: false,
+        });
+        self.todos.last().unwrap()
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut Todo> {
+        self.todos.iter_mut().find(|todo| todo.id == id)
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<Todo> {
+        let index = self.todos.iter().position(|todo| todo.id == id)?;
+        Some(self.todos.remove(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Todo> {
+        self.todos.iter()
+    }
+}
+
+impl Queryable for Todo {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn kind(&self) -> AssignmentKind {
+        AssignmentKind::Todo
+    }
+
+    fn course_id(&self) -> &str {
+        self.course_id.as_deref().unwrap_or_default()
+    }
+
+    fn course_title(&self) -> &str {
+        self.course_title.as_deref().unwrap_or_default()
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn state(&self) -> AssignmentState {
+        if self.done {
+            AssignmentState::Done
+        } else {
+            AssignmentState::Todo
+        }
+    }
+
+    fn due_date(&self) -> Option<DateTime<Utc>> {
+        self.due_date
+    }
+}
@@ -0,0 +1,80 @@
+use chrono::Weekday;
+use manaba_sdk::error::Result;
+use manaba_sdk::{Course, Term, TimetableEntry};
+
+const LIST_VIEW: &str = include_str!("fixtures/home_course_list.html");
+const THUMBNAIL_VIEW: &str = include_str!("fixtures/home_course_thumbnail.html");
+const TIMETABLE_VIEW: &str = include_str!("fixtures/home_course_timetable.html");
+
+#[test]
+fn course_list_test() -> Result<()> {
+    let courses = Course::from_html(LIST_VIEW)?;
+
+    assert_eq!(courses.len(), 2);
+
+    let course = &courses[0];
+    assert_eq!(course.id, "course_12345");
+    assert_eq!(course.title, "12345:データ構造とアルゴリズム");
+    assert_eq!(course.year, Some(2026));
+    assert_eq!(course.term, Some(Term::Second));
+    assert_eq!(course.instructors, ["山田 太郎", "佐藤 花子"]);
+    assert_eq!(course.schedule.as_deref(), Some("月1"));
+    assert_eq!(course.status.as_deref(), Some("開講中"));
+
+    // The term is read from the year column when its own column is empty
+    let course = &courses[1];
+    assert_eq!(course.term, Some(Term::FullYear));
+    assert_eq!(course.instructors, ["John Smith", "Jane Doe"]);
+    assert_eq!(course.status, None);
+
+    Ok(())
+}
+
+#[test]
+fn course_links_test() -> Result<()> {
+    let mut courses = Course::from_html(THUMBNAIL_VIEW)?;
+    courses.sort_by(|a, b| a.id.cmp(&b.id));
+
+    // Links without a course code in the title are left out
+    let ids = courses
+        .iter()
+        .map(|course| course.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["course_12345", "course_23456"]);
+    assert_eq!(courses[0].title, "12345:データ構造とアルゴリズム");
+    assert_eq!(courses[0].year, None);
+
+    Ok(())
+}
+
+#[test]
+fn timetable_test() -> Result<()> {
+    let entries = TimetableEntry::from_html(TIMETABLE_VIEW)?;
+
+    let slots = entries
+        .iter()
+        .map(|entry| (entry.weekday, entry.period, entry.course.id.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        slots,
+        [
+            (Weekday::Mon, 1, "course_12345"),
+            (Weekday::Thu, 2, "course_23456"),
+            (Weekday::Thu, 2, "course_45678"),
+        ]
+    );
+
+    assert_eq!(entries[0].course.title, "12345:データ構造とアルゴリズム");
+    assert_eq!(entries[0].room.as_deref(), Some("C201"));
+    assert_eq!(entries[1].room, None);
+
+    Ok(())
+}
+
+#[test]
+fn timetable_in_other_views_test() -> Result<()> {
+    assert!(TimetableEntry::from_html(LIST_VIEW)?.is_empty());
+    assert!(TimetableEntry::from_html(THUMBNAIL_VIEW)?.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use manaba_sdk::assignment::{AssignmentReceptibleState, AssignmentSubmitState};
+use manaba_sdk::overlay::{LocalState, LocalStatus, Overlay, apply_status};
+use manaba_sdk::query::{AssignmentQuery, AssignmentState, Queryable};
+use manaba_sdk::{Course, Report};
+
+fn course() -> Course {
+    Course {
+        title: "11111:Mathematics I".to_owned(),
+        id: "course_11111".to_owned(),
+        ..Default::default()
+    }
+}
+
+fn report(id: &str) -> Report {
+    Report {
+        id: id.to_owned(),
+        title: id.to_owned(),
+        submit_state: AssignmentSubmitState::Todo,
+        receptiable_state: AssignmentReceptibleState::Open,
+        start_date: None,
+        due_date: None,
+    }
+}
+
+fn marked(status: LocalStatus) -> LocalState {
+    LocalState {
+        status: Some(status),
+        note: None,
+    }
+}
+
+#[test]
+fn apply_status_test() {
+    assert_eq!(
+        apply_status(AssignmentState::Todo, Some(LocalStatus::Started)),
+        AssignmentState::Todo
+    );
+    assert_eq!(
+        apply_status(AssignmentState::Todo, Some(LocalStatus::Ignored)),
+        AssignmentState::Ignored
+    );
+    assert_eq!(
+        apply_status(AssignmentState::Closed, Some(LocalStatus::DoneOffline)),
+        AssignmentState::Done
+    );
+    assert_eq!(
+        apply_status(AssignmentState::Waiting, None),
+        AssignmentState::Waiting
+    );
+}
+
+#[test]
+fn wrap_applies_local_status_test() {
+    let course = course();
+    let ignored = report("course_11111_report_1");
+    let started = report("course_11111_report_2");
+
+    let mut overlay = Overlay::default();
+    overlay.set(&ignored.id, marked(LocalStatus::Ignored));
+    overlay.set(&started.id, marked(LocalStatus::Started));
+
+    let todo = AssignmentQuery {
+        states: vec![AssignmentState::Todo],
+        ..Default::default()
+    };
+
+    assert!(!todo.matches(&overlay.wrap((&course, &ignored))));
+    assert!(todo.matches(&overlay.wrap((&course, &started))));
+    assert_eq!(
+        overlay.wrap((&course, &ignored)).state(),
+        AssignmentState::Ignored
+    );
+}
+
+#[test]
+fn set_empty_removes_test() {
+    let mut overlay = Overlay::default();
+    overlay.set("course_11111_report_1", marked(LocalStatus::Started));
+    overlay.set("course_11111_report_1", LocalState::default());
+
+    assert!(overlay.get("course_11111_report_1").is_none());
+}
+
+#[test]
+fn save_and_load_test() {
+    let path = std::env::temp_dir()
+        .join(format!("manaba-overlay-test-{}", std::process::id()))
+        .join("overlay.json");
+
+    let mut overlay = Overlay::default();
+    overlay.set(
+        "course_11111_report_1",
+        LocalState {
+            status: Some(LocalStatus::DoneOffline),
+            note: Some("handed in on paper".to_owned()),
+        },
+    );
+    overlay.save(&path).unwrap();
+
+    let loaded = Overlay::load(&path).unwrap();
+    let local = loaded.get("course_11111_report_1").unwrap();
+    assert_eq!(local.status, Some(LocalStatus::DoneOffline));
+    assert_eq!(local.note.as_deref(), Some("handed in on paper"));
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    // A missing file is an empty overlay
+    assert!(
+        Overlay::load(&path)
+            .unwrap()
+            .get("course_11111_report_1")
+            .is_none()
+    );
+}
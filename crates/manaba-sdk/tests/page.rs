@@ -0,0 +1,32 @@
+use manaba_sdk::PageText;
+use manaba_sdk::error::Result;
+
+const REPORT_DETAIL: &str = include_str!("fixtures/report_detail.html");
+
+#[test]
+fn page_text_test() -> Result<()> {
+    let page = PageText::from_html(REPORT_DETAIL)?;
+
+    assert_eq!(
+        page.lines,
+        [
+            "タイトル",
+            "第3回 課題",
+            "課題内容",
+            "教科書 3 章の演習を解き、 PDF で提出してください。",
+            "参考: 資料",
+            "受付終了日時",
+            "2026-10-20 23:59",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn page_text_without_content_test() -> Result<()> {
+    let page = PageText::from_html("<html><body><p>Login</p><p> required </p></body></html>")?;
+    assert_eq!(page.lines, ["Login", "required"]);
+
+    Ok(())
+}
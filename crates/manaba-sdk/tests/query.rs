@@ -0,0 +1,151 @@
+use chrono::{TimeZone, Utc};
+use manaba_sdk::assignment::{
+    AssignmentDate, AssignmentKind, AssignmentReceptibleState, AssignmentSubmitState,
+};
+use manaba_sdk::query::{AssignmentQuery, AssignmentState, Queryable, SortKey};
+use manaba_sdk::{Course, Report, Tz};
+
+fn course(id: &str, title: &str) -> Course {
+    Course {
+        title: title.to_owned(),
+        id: id.to_owned(),
+        ..Default::default()
+    }
+}
+
+fn report(title: &str, due_date: Option<&str>) -> Report {
+    Report {
+        id: format!("{title}_report"),
+        title: title.to_owned(),
+        submit_state: AssignmentSubmitState::Todo,
+        receptiable_state: AssignmentReceptibleState::Open,
+        start_date: None,
+        due_date: due_date.map(|date| AssignmentDate::new(date, Tz::Asia__Tokyo)),
+    }
+}
+
+#[test]
+fn assignment_state_test() {
+    use AssignmentReceptibleState::*;
+    use AssignmentSubmitState::*;
+
+    assert_eq!(AssignmentState::new(Todo, Open), AssignmentState::Todo);
+    assert_eq!(AssignmentState::new(Todo, Closed), AssignmentState::Closed);
+    assert_eq!(AssignmentState::new(Done, Open), AssignmentState::Done);
+    assert_eq!(AssignmentState::new(Done, Closed), AssignmentState::Done);
+    assert_eq!(
+        AssignmentState::new(Done, NotStarted),
+        AssignmentState::Waiting
+    );
+}
+
+#[test]
+fn matches_course_and_search_test() {
+    let math = course("course_11111", "11111:Mathematics I");
+    let physics = course("course_22222", "22222:Physics");
+    let essay = report("Weekly Essay", None);
+
+    let query = AssignmentQuery {
+        course: Some("math".to_owned()),
+        search: Some("essay".to_owned()),
+        ..Default::default()
+    };
+
+    assert!(query.matches(&(&math, &essay)));
+    assert!(!query.matches(&(&physics, &essay)));
+    assert!(query.matches_course(&math));
+
+    let by_id = AssignmentQuery {
+        course: Some("22222".to_owned()),
+        ..Default::default()
+    };
+    assert!(by_id.matches(&(&physics, &essay)));
+}
+
+#[test]
+fn matches_due_range_test() {
+    let math = course("course_11111", "11111:Mathematics I");
+    let query = AssignmentQuery {
+        // 2026-10-19 00:00 to 2026-10-26 00:00 in Asia/Tokyo
+        due_after: Some(Utc.with_ymd_and_hms(2026, 10, 18, 15, 0, 0).unwrap()),
+        due_before: Some(Utc.with_ymd_and_hms(2026, 10, 25, 15, 0, 0).unwrap()),
+        ..Default::default()
+    };
+
+    assert!(query.matches(&(&math, &report("a", Some("2026-10-19 00:00")))));
+    assert!(query.matches(&(&math, &report("b", Some("2026-10-25 23:59")))));
+    assert!(!query.matches(&(&math, &report("c", Some("2026-10-26 00:00")))));
+    assert!(!query.matches(&(&math, &report("d", Some("2026-10-18 23:59")))));
+    // Assignments without a deadline never match a date range
+    assert!(!query.matches(&(&math, &report("e", None))));
+}
+
+#[test]
+fn matches_state_and_kind_test() {
+    let math = course("course_11111", "11111:Mathematics I");
+    let mut done = report("done", None);
+    done.submit_state = AssignmentSubmitState::Done;
+    let todo = report("todo", None);
+
+    let query = AssignmentQuery {
+        states: vec![AssignmentState::Done],
+        ..Default::default()
+    };
+    assert!(query.matches(&(&math, &done)));
+    assert!(!query.matches(&(&math, &todo)));
+
+    let exams_only = AssignmentQuery {
+        kinds: vec![AssignmentKind::Exam],
+        ..Default::default()
+    };
+    assert!(!exams_only.matches(&(&math, &todo)));
+    assert!(exams_only.includes_kind(AssignmentKind::Exam));
+}
+
+#[test]
+fn sort_test() {
+    let math = course("course_11111", "11111:Mathematics I");
+    let physics = course("course_22222", "22222:Physics");
+    let reports = [
+        report("b", None),
+        report("c", Some("2026-10-20 10:00")),
+        report("a", Some("2026-10-19 10:00")),
+    ];
+
+    let items = vec![
+        (&physics, &reports[0]),
+        (&physics, &reports[1]),
+        (&math, &reports[2]),
+    ];
+    let titles = |sort| {
+        let query = AssignmentQuery {
+            sort: Some(sort),
+            ..Default::default()
+        };
+        query
+            .apply(items.clone())
+            .iter()
+            .map(|item| item.title().to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(titles(SortKey::Due), ["a", "c", "b"]);
+    assert_eq!(titles(SortKey::Course), ["a", "c", "b"]);
+    assert_eq!(titles(SortKey::Title), ["a", "b", "c"]);
+}
+
+#[test]
+fn deserialize_query_test() {
+    let query: AssignmentQuery = serde_json::from_str(
+        r#"{"course": "math", "states": ["todo"], "due_before": "2026-10-26T00:00:00+09:00", "sort": "due"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(query.course.as_deref(), Some("math"));
+    assert_eq!(query.states, [AssignmentState::Todo]);
+    assert_eq!(
+        query.due_before,
+        Some(Utc.with_ymd_and_hms(2026, 10, 25, 15, 0, 0).unwrap())
+    );
+    assert_eq!(query.sort, Some(SortKey::Due));
+}
@@ -41,3 +41,42 @@ async fn get_reports_test() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn get_news_test() -> Result<()> {
+    let cookie = Cookie::load(COOKIE_DOMAIN)?;
+
+    let client = Client::new(BASE_URL, &cookie).await?;
+    let courses = client.get_courses().await?;
+
+    for course in courses {
+        let _news = client.get_news(&course).await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_timetable_test() -> Result<()> {
+    let cookie = Cookie::load(COOKIE_DOMAIN)?;
+
+    let client = Client::new(BASE_URL, &cookie).await?;
+    let _timetable = client.get_timetable().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn check_selectors_test() -> Result<()> {
+    let cookie = Cookie::load(COOKIE_DOMAIN)?;
+
+    let client = Client::new(BASE_URL, &cookie).await?;
+    let courses = client.get_courses().await?;
+    let checks = client.check_selectors(courses.first()).await;
+
+    for check in checks.iter().filter(|check| check.scraper == "courses") {
+        assert!(check.matches.as_ref().is_ok_and(|&count| count > 0));
+    }
+
+    Ok(())
+}
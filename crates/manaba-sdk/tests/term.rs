@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use manaba_sdk::{AcademicTerm, Course, Term};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn course(year: Option<i32>, term: Option<Term>) -> Course {
+    Course {
+        title: "12345:Course".to_owned(),
+        id: "course_12345".to_owned(),
+        year,
+        term,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn academic_term_at_test() {
+    assert_eq!(
+        AcademicTerm::at(date(2026, 4, 1)),
+        AcademicTerm {
+            year: 2026,
+            term: Term::First
+        }
+    );
+    assert_eq!(
+        AcademicTerm::at(date(2026, 9, 30)),
+        AcademicTerm {
+            year: 2026,
+            term: Term::First
+        }
+    );
+    assert_eq!(
+        AcademicTerm::at(date(2026, 10, 1)),
+        AcademicTerm {
+            year: 2026,
+            term: Term::Second
+        }
+    );
+    // January to March belongs to the previous academic year
+    assert_eq!(
+        AcademicTerm::at(date(2027, 3, 31)),
+        AcademicTerm {
+            year: 2026,
+            term: Term::Second
+        }
+    );
+}
+
+#[test]
+fn is_held_in_test() {
+    let current = AcademicTerm {
+        year: 2026,
+        term: Term::Second,
+    };
+
+    assert!(course(Some(2026), Some(Term::Second)).is_held_in(&current));
+    assert!(course(Some(2026), Some(Term::FullYear)).is_held_in(&current));
+    assert!(!course(Some(2026), Some(Term::First)).is_held_in(&current));
+    assert!(!course(Some(2025), Some(Term::Second)).is_held_in(&current));
+}
+
+#[test]
+fn is_held_in_unknown_test() {
+    let current = AcademicTerm {
+        year: 2026,
+        term: Term::First,
+    };
+
+    assert!(course(None, None).is_held_in(&current));
+    assert!(course(Some(2026), None).is_held_in(&current));
+    assert!(!course(Some(2024), None).is_held_in(&current));
+}
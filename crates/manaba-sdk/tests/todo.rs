@@ -0,0 +1,94 @@
+use chrono::{TimeZone, Utc};
+use manaba_sdk::Tz;
+use manaba_sdk::assignment::AssignmentKind;
+use manaba_sdk::query::{AssignmentQuery, AssignmentState, Queryable};
+use manaba_sdk::todo::TodoList;
+
+#[test]
+fn add_and_remove_test() {
+    let mut todos = TodoList::default();
+    let first = todos
+        .add("Read chapter 3".to_owned(), None, None)
+        .id
+        .clone();
+    let second = todos
+        .add(
+            "Prepare slides".to_owned(),
+            Some((
+                "course_11111".to_owned(),
+                "11111:Operating Systems".to_owned(),
+            )),
+            None,
+        )
+        .id
+        .clone();
+
+    assert_eq!(first, "todo_1");
+    assert_eq!(second, "todo_2");
+
+    assert!(todos.remove(&first).is_some());
+    assert!(todos.remove(&first).is_none());
+
+    // Ids are never reused after a removal
+    let third = todos.add("Review notes".to_owned(), None, None).id.clone();
+    assert_eq!(third, "todo_3");
+    assert_eq!(todos.iter().count(), 2);
+}
+
+#[test]
+fn queryable_test() {
+    let mut todos = TodoList::default();
+    let due_date = Utc.with_ymd_and_hms(2026, 10, 20, 14, 59, 0).unwrap();
+    let id = todos
+        .add(
+            "Read chapter 3".to_owned(),
+            Some((
+                "course_11111".to_owned(),
+                "11111:Operating Systems".to_owned(),
+            )),
+            Some(due_date),
+        )
+        .id
+        .clone();
+
+    let todo = todos.iter().next().unwrap();
+    assert_eq!(todo.kind(), AssignmentKind::Todo);
+    assert_eq!(todo.state(), AssignmentState::Todo);
+    assert_eq!(todo.course_title(), "11111:Operating Systems");
+    assert_eq!(
+        todo.due(Tz::Asia__Tokyo).unwrap().date.to_rfc3339(),
+        "2026-10-20T23:59:00+09:00"
+    );
+
+    let query = AssignmentQuery {
+        course: Some("operating".to_owned()),
+        states: vec![AssignmentState::Todo],
+        ..Default::default()
+    };
+    assert!(query.matches(todo));
+
+    todos.get_mut(&id).unwrap().done = true;
+    let todo = todos.iter().next().unwrap();
+    assert_eq!(todo.state(), AssignmentState::Done);
+    assert!(!query.matches(todo));
+}
+
+#[test]
+fn save_and_load_test() {
+    let path = std::env::temp_dir()
+        .join(format!("manaba-todo-test-{}", std::process::id()))
+        .join("todos.json");
+
+    let mut todos = TodoList::default();
+    todos.add("Read chapter 3".to_owned(), None, None);
+    todos.save(&path).unwrap();
+
+    let mut loaded = TodoList::load(&path).unwrap();
+    assert_eq!(loaded.iter().next().unwrap().title, "Read chapter 3");
+    assert_eq!(loaded.add("Next".to_owned(), None, None).id, "todo_2");
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    // A missing file is an empty list
+    assert_eq!(TodoList::load(&path).unwrap().iter().count(), 0);
+}
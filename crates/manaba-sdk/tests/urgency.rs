@@ -0,0 +1,183 @@
+use chrono::{TimeZone, Utc};
+use manaba_sdk::Tz;
+use manaba_sdk::assignment::{AssignmentDate, AssignmentImportanceLevel, UrgencyPolicy};
+use manaba_sdk::clock::FixedClock;
+
+const DUE_DATE: &str = "2026-10-25 23:59";
+
+/// 2026-10-25 23:59 in Asia/Tokyo
+fn due_date() -> AssignmentDate {
+    AssignmentDate::new(DUE_DATE, Tz::Asia__Tokyo)
+}
+
+/// A clock `hours` and `minutes` before the deadline
+fn clock_before(hours: i64, minutes: i64) -> FixedClock {
+    let due = Utc.with_ymd_and_hms(2026, 10, 25, 14, 59, 0).unwrap();
+    FixedClock(due - chrono::Duration::hours(hours) - chrono::Duration::minutes(minutes))
+}
+
+#[test]
+fn parse_in_institution_timezone_test() {
+    let date = due_date();
+
+    assert_eq!(
+        date.date.with_timezone(&Utc),
+        Utc.with_ymd_and_hms(2026, 10, 25, 14, 59, 0).unwrap()
+    );
+}
+
+/// The thresholds of `UrgencyPolicy::default()`, counted in hours
+fn hourly_policy() -> UrgencyPolicy {
+    UrgencyPolicy {
+        high_hours: Some(72),
+        medium_hours: Some(168),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn calendar_days_by_default_test() {
+    let policy = UrgencyPolicy::default();
+
+    // 10/22 00:00 and 10/21 23:59 in Tokyo
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(95, 59)),
+        AssignmentImportanceLevel::High
+    );
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(96, 0)),
+        AssignmentImportanceLevel::Medium
+    );
+    // 10/18 00:00 and 10/17 23:59 in Tokyo
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(191, 59)),
+        AssignmentImportanceLevel::Medium
+    );
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(192, 0)),
+        AssignmentImportanceLevel::Low
+    );
+}
+
+#[test]
+fn high_boundary_test() {
+    let policy = hourly_policy();
+
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(72, 0)),
+        AssignmentImportanceLevel::High
+    );
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(72, 1)),
+        AssignmentImportanceLevel::Medium
+    );
+}
+
+#[test]
+fn medium_boundary_test() {
+    let policy = hourly_policy();
+
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(168, 0)),
+        AssignmentImportanceLevel::Medium
+    );
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(168, 1)),
+        AssignmentImportanceLevel::Low
+    );
+}
+
+#[test]
+fn custom_thresholds_test() {
+    let policy = UrgencyPolicy {
+        high_hours: Some(24),
+        medium_hours: Some(72),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(25, 0)),
+        AssignmentImportanceLevel::Medium
+    );
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(73, 0)),
+        AssignmentImportanceLevel::Low
+    );
+}
+
+#[test]
+fn past_deadline_test() {
+    let clock = clock_before(0, -1);
+
+    assert_eq!(
+        due_date().importance_level(&UrgencyPolicy::default(), &clock),
+        AssignmentImportanceLevel::None
+    );
+
+    let policy = UrgencyPolicy {
+        overdue: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        due_date().importance_level(&policy, &clock),
+        AssignmentImportanceLevel::Overdue
+    );
+}
+
+#[test]
+fn due_today_test() {
+    let policy = UrgencyPolicy {
+        due_today: true,
+        ..Default::default()
+    };
+
+    // 00:00 on the same day in Tokyo
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(23, 59)),
+        AssignmentImportanceLevel::DueToday
+    );
+    // 23:59 on the previous day in Tokyo
+    assert_eq!(
+        due_date().importance_level(&policy, &clock_before(24, 0)),
+        AssignmentImportanceLevel::High
+    );
+}
+
+#[test]
+fn due_today_in_display_timezone_test() {
+    let policy = UrgencyPolicy {
+        due_today: true,
+        ..Default::default()
+    };
+    let due = due_date();
+
+    // 10/25 01:00 in UTC is still the deadline day there, but not in New York (10/24 21:00)
+    let now = Utc.with_ymd_and_hms(2026, 10, 25, 1, 0, 0).unwrap();
+    assert_eq!(
+        policy.importance_level(&due.date, &now),
+        AssignmentImportanceLevel::DueToday
+    );
+    assert_eq!(
+        policy.importance_level(&due.date, &now.with_timezone(&Tz::America__New_York)),
+        AssignmentImportanceLevel::High
+    );
+}
+
+#[test]
+fn validate_thresholds_test() {
+    let policy = UrgencyPolicy {
+        high_hours: Some(24),
+        ..Default::default()
+    };
+    assert!(policy.validate().is_ok());
+
+    let policy = UrgencyPolicy {
+        high_hours: Some(72),
+        medium_hours: Some(24),
+        ..Default::default()
+    };
+    assert_eq!(
+        policy.validate(),
+        Err("high_hours (72) must not be greater than medium_hours (24)".to_owned())
+    );
+}